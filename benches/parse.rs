@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use influxlp_tools::LineProtocol;
+
+fn parse_lines(c: &mut Criterion) {
+    let lines = (0..100_000)
+        .map(|i| {
+            format!(
+                "measurement,host=server{},region=eu integer={i}i,float={i}.5,string=\"value \
+                 {i}\",boolean=true {}",
+                i % 100,
+                1729270461612452700i64 + i
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    c.bench_function("parse 100k lines", |b| {
+        b.iter(|| LineProtocol::parse_chunks(black_box(&lines), 5000).count())
+    });
+}
+
+criterion_group!(benches, parse_lines);
+criterion_main!(benches);
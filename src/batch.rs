@@ -0,0 +1,905 @@
+//! Batch methods operate on a slice of [LineProtocol] structs at once, e.g.,
+//! the result of [LineProtocol::parse_lines] or a set of data points about to
+//! be written to InfluxDB
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use crate::{
+    diff::diff_maps,
+    element::{FieldKey, FieldValue, Measurement},
+    error::{BuilderError, LineProtocolError, MixedMeasurements},
+    precision::TimestampPrecision,
+    LineProtocol,
+};
+
+/// A callback invoked with each built line and its length in bytes, see
+/// [BatchBuilder::on_build]
+pub type OnBuild = Arc<dyn Fn(&str, usize) + Send + Sync>;
+
+/// Builds a batch of data points into a single line protocol string with one
+/// line per data point
+///
+/// # Example
+/// ```rust
+/// let batch = BatchBuilder::new()
+///     .add_point(LineProtocol::new("measurement").add_field("field", 1))
+///     .add_point(LineProtocol::new("measurement").add_field("field", 2))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct BatchBuilder {
+    points: Vec<LineProtocol>,
+    on_build: Option<OnBuild>,
+}
+
+impl std::fmt::Debug for BatchBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchBuilder")
+            .field("points", &self.points)
+            .field("on_build", &self.on_build.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+impl BatchBuilder {
+    /// Create a new empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a data point to the batch
+    ///
+    /// This function is useful if you want to follow a builder pattern
+    ///
+    /// # Args
+    /// * `point` - The data point to add
+    pub fn add_point(mut self, point: LineProtocol) -> Self {
+        self.points.push(point);
+        self
+    }
+
+    /// Add a data point to the batch
+    ///
+    /// This function is useful if you want to build a batch dynamically
+    ///
+    /// # Args
+    /// * `point` - The data point to add
+    pub fn add_point_ref(&mut self, point: LineProtocol) {
+        self.points.push(point);
+    }
+
+    /// Set a callback invoked with each built line and its length in bytes,
+    /// e.g., to emit per series write metrics
+    ///
+    /// # Example
+    /// ```rust
+    /// let batch = BatchBuilder::new()
+    ///     .on_build(|line, len| println!("built {len} bytes: {line}"))
+    ///     .add_point(line_protocol);
+    /// ```
+    ///
+    /// # Args
+    /// * `f` - The callback
+    pub fn on_build<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str, usize) + Send + Sync + 'static,
+    {
+        self.on_build = Some(Arc::new(f));
+        self
+    }
+
+    /// Get the data points in the batch
+    pub fn points(&self) -> &[LineProtocol] {
+        &self.points
+    }
+
+    /// Get the amount of data points in the batch
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Check if the batch contains no data points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Build every data point and join them with a newline
+    ///
+    /// Fails on the first data point which fails to build
+    pub fn build(&self) -> Result<String, LineProtocolError> {
+        let mut batch = String::new();
+        for (index, point) in self.points.iter().enumerate() {
+            let line = point.build()?;
+            if let Some(on_build) = &self.on_build {
+                on_build(&line, line.len());
+            }
+
+            if index > 0 {
+                batch.push('\n');
+            }
+            batch.push_str(&line);
+        }
+
+        Ok(batch)
+    }
+}
+
+/// Check that the data points in a batch are ordered by non-decreasing
+/// timestamp
+///
+/// Data points without a timestamp are ignored as InfluxDB assigns them the
+/// time they are received, meaning they can't be out of order in relation to
+/// the other data points
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+/// if !is_timestamp_monotonic(&points) {
+///     points.sort_by_key(|lp| lp.timestamp);
+/// }
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn is_timestamp_monotonic(points: &[LineProtocol]) -> bool {
+    assert_monotonic(points).is_ok()
+}
+
+/// Check that the data points in a batch are ordered by non-decreasing
+/// timestamp, returning the index of the first data point that is out of order
+///
+/// Data points without a timestamp are ignored, see [is_timestamp_monotonic]
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+/// if let Err(index) = assert_monotonic(&points) {
+///     println!("data point {index} is out of order");
+/// }
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn assert_monotonic(points: &[LineProtocol]) -> Result<(), usize> {
+    let mut previous = None;
+    for (index, point) in points.iter().enumerate() {
+        let Some(timestamp) = point.timestamp else {
+            continue;
+        };
+
+        if previous.is_some_and(|previous| timestamp < previous) {
+            return Err(index);
+        }
+        previous = Some(timestamp);
+    }
+
+    Ok(())
+}
+
+/// Infer the timestamp precision of a batch
+///
+/// The precision of each timestamp is guessed with
+/// [TimestampPrecision::guess]. A precision is only returned if every data
+/// point with a timestamp agrees on it, otherwise `None` is returned. `None` is
+/// also returned if no data point has a timestamp
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+/// let precision = infer_batch_precision(&points).unwrap_or_default();
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn infer_batch_precision(points: &[LineProtocol]) -> Option<TimestampPrecision> {
+    let mut precisions = points
+        .iter()
+        .filter_map(|point| point.timestamp)
+        .map(TimestampPrecision::guess);
+
+    let precision = precisions.next()?;
+    precisions
+        .all(|other| other == precision)
+        .then_some(precision)
+}
+
+/// Build a batch of data points into a single byte buffer with one line per
+/// data point, e.g., for use as the body of an HTTP request
+///
+/// The lines are joined with a newline, without a trailing newline. An error
+/// is returned if any data point fails to build
+///
+/// # Example
+/// ```rust
+/// let body = batch_to_bytes(&points).unwrap();
+/// let request = client.post(url).body(body);
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn batch_to_bytes(points: &[LineProtocol]) -> Result<Vec<u8>, LineProtocolError> {
+    let mut bytes = Vec::new();
+    for (index, point) in points.iter().enumerate() {
+        if index > 0 {
+            bytes.push(b'\n');
+        }
+        point.write_bytes(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// Build a batch of data points into newline-joined chunks of at most
+/// `max_bytes` bytes each, e.g., to stay below the request body size limit of
+/// the write API
+///
+/// The lines are packed greedily in order and a line is never split across
+/// chunks. The newlines joining the lines count towards the size, there is no
+/// trailing newline. An error is returned if any data point fails to build or
+/// a single line exceeds `max_bytes` on its own
+///
+/// # Example
+/// ```rust
+/// for body in chunk_by_bytes(&points, 5_000_000).unwrap() {
+///     client.post(url).body(body).send().unwrap();
+/// }
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+/// * `max_bytes` - The maximum size of a chunk in bytes
+pub fn chunk_by_bytes(
+    points: &[LineProtocol],
+    max_bytes: usize,
+) -> Result<Vec<String>, LineProtocolError> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    for point in points {
+        let line = point.build()?;
+        if line.len() > max_bytes {
+            return Err(BuilderError::LineTooLarge {
+                bytes: line.len(),
+                max: max_bytes,
+            }
+            .into());
+        }
+
+        if !chunk.is_empty() && chunk.len() + 1 + line.len() > max_bytes {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+
+        if !chunk.is_empty() {
+            chunk.push('\n');
+        }
+        chunk.push_str(&line);
+    }
+
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+
+    Ok(chunks)
+}
+
+/// Check that all data points in a batch share the same measurement
+///
+/// Returns the shared measurement, or an error listing the distinct
+/// measurements found. An empty batch has no shared measurement and returns
+/// an error without any measurements
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+/// let measurement = assert_single_measurement(&points).unwrap();
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn assert_single_measurement(
+    points: &[LineProtocol],
+) -> Result<&Measurement, MixedMeasurements> {
+    let mut measurements: Vec<&Measurement> = Vec::new();
+    for point in points {
+        if !measurements.contains(&&point.measurement) {
+            measurements.push(&point.measurement);
+        }
+    }
+
+    match measurements.as_slice() {
+        [measurement] => Ok(measurement),
+        _ => Err(MixedMeasurements {
+            measurements: measurements.into_iter().cloned().collect(),
+        }),
+    }
+}
+
+/// A summary of a batch of data points
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// The amount of data points
+    pub points: usize,
+
+    /// The amount of unique series, i.e., unique measurement and tag set
+    /// combinations
+    pub series: usize,
+
+    /// The lowest timestamp, `None` if no data point has a timestamp
+    pub min_timestamp: Option<i64>,
+
+    /// The highest timestamp, `None` if no data point has a timestamp
+    pub max_timestamp: Option<i64>,
+
+    /// The total amount of fields across all data points
+    pub fields: usize,
+}
+
+/// Summarize a batch of data points, e.g., for logging after building a batch
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+/// let summary = batch_summary(&points);
+/// println!(
+///     "writing {} points across {} series",
+///     summary.points, summary.series
+/// );
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+pub fn batch_summary(points: &[LineProtocol]) -> BatchSummary {
+    let mut series = HashSet::new();
+    let mut summary = BatchSummary {
+        points: points.len(),
+        ..Default::default()
+    };
+
+    for point in points {
+        series.insert(point.series_key());
+        summary.fields += point.fields.len();
+
+        if let Some(timestamp) = point.timestamp {
+            summary.min_timestamp = Some(
+                summary
+                    .min_timestamp
+                    .map_or(timestamp, |min| min.min(timestamp)),
+            );
+            summary.max_timestamp = Some(
+                summary
+                    .max_timestamp
+                    .map_or(timestamp, |max| max.max(timestamp)),
+            );
+        }
+    }
+
+    summary.series = series.len();
+    summary
+}
+
+/// Merge data points of the same series whose timestamps are close in time
+///
+/// The data points are sorted by timestamp and grouped by series, see
+/// [LineProtocol::series_key]. A data point within `window` of the first data
+/// point of the current group is merged into it, where the fields of the later
+/// data point are favored. The merged data point keeps the timestamp of the
+/// first data point in the group
+///
+/// Timestamps are compared in nanoseconds, so data points of different
+/// precisions are merged correctly. Data points without a timestamp are never
+/// merged
+///
+/// # Example
+/// ```rust
+/// let points = LineProtocol::parse_lines(lines).unwrap();
+///
+/// // Merge data points less than a second apart
+/// let merged = merge_within_window(points, 1_000_000_000);
+/// ```
+///
+/// # Args
+/// * `points` - A batch of data points
+/// * `window` - The maximum distance in nanoseconds between two timestamps for
+///   their data points to be merged
+pub fn merge_within_window(mut points: Vec<LineProtocol>, window: i64) -> Vec<LineProtocol> {
+    points.sort_by_key(|point| point.timestamp_nanos());
+
+    let mut merged: Vec<LineProtocol> = Vec::new();
+    let mut groups: HashMap<String, usize> = HashMap::new();
+    for point in points {
+        let Some(timestamp) = point.timestamp_nanos() else {
+            merged.push(point);
+            continue;
+        };
+
+        let series_key = point.series_key();
+        if let Some(&index) = groups.get(&series_key) {
+            let group = &mut merged[index];
+            if group
+                .timestamp_nanos()
+                .is_some_and(|start| timestamp - start <= i128::from(window))
+            {
+                group.fields.extend(point.fields);
+                continue;
+            }
+        }
+
+        groups.insert(series_key, merged.len());
+        merged.push(point);
+    }
+
+    merged
+}
+
+/// A series present in both batches whose fields have changed
+#[derive(Debug, Clone)]
+pub struct ChangedSeries {
+    /// The series key, see [LineProtocol::series_key]
+    pub series: String,
+
+    /// The data point in the old batch
+    pub old: LineProtocol,
+
+    /// The data point in the new batch
+    pub new: LineProtocol,
+
+    /// Fields only present in the new data point
+    pub fields_added: HashMap<FieldKey, FieldValue>,
+
+    /// Fields only present in the old data point
+    pub fields_removed: HashMap<FieldKey, FieldValue>,
+
+    /// Fields present in both data points with a different value, as the old
+    /// and new value
+    pub fields_changed: HashMap<FieldKey, (FieldValue, FieldValue)>,
+}
+
+/// The difference between two batches of data points by series
+#[derive(Debug, Clone, Default)]
+pub struct BatchDiff {
+    /// Data points whose series is only present in the new batch
+    pub added: Vec<LineProtocol>,
+
+    /// Data points whose series is only present in the old batch
+    pub removed: Vec<LineProtocol>,
+
+    /// Series present in both batches with different fields
+    pub changed: Vec<ChangedSeries>,
+}
+
+impl BatchDiff {
+    /// Check if the batches contain the same series with the same fields
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two batches of data points by series, see
+/// [LineProtocol::series_key], and report which series are added, removed, or
+/// have changed fields
+///
+/// If a batch contains multiple data points of the same series, the last one
+/// is used. Only the fields are compared, a series whose timestamp differs but
+/// has the same fields is not considered changed. The results are sorted by
+/// series key
+///
+/// # Example
+/// ```rust
+/// let old = LineProtocol::parse_lines(old_lines).unwrap();
+/// let new = LineProtocol::parse_lines(new_lines).unwrap();
+///
+/// let diff = diff_batches(&old, &new);
+/// for changed in diff.changed {
+///     println!(
+///         "{} changed {} fields",
+///         changed.series,
+///         changed.fields_changed.len()
+///     );
+/// }
+/// ```
+///
+/// # Args
+/// * `old` - The previous batch of data points
+/// * `new` - The current batch of data points
+pub fn diff_batches(old: &[LineProtocol], new: &[LineProtocol]) -> BatchDiff {
+    let old = by_series(old);
+    let mut new = by_series(new);
+
+    let mut diff = BatchDiff::default();
+    for (series, old_point) in old {
+        let Some(new_point) = new.remove(&series) else {
+            diff.removed.push(old_point.clone());
+            continue;
+        };
+
+        let fields = diff_maps(&old_point.fields, &new_point.fields);
+        if fields.added.is_empty() && fields.removed.is_empty() && fields.changed.is_empty() {
+            continue;
+        }
+
+        diff.changed.push(ChangedSeries {
+            series,
+            old: old_point.clone(),
+            new: new_point.clone(),
+            fields_added: fields.added,
+            fields_removed: fields.removed,
+            fields_changed: fields.changed,
+        });
+    }
+
+    diff.added = new.into_values().cloned().collect();
+    diff
+}
+
+/// Map each series key to the last data point of the series
+fn by_series(points: &[LineProtocol]) -> BTreeMap<String, &LineProtocol> {
+    points
+        .iter()
+        .map(|point| (point.series_key(), point))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_batch_monotonic() {
+        let points = vec![
+            LineProtocol::new("measurement")
+                .add_field("field", 1)
+                .with_timestamp(1i64),
+            LineProtocol::new("measurement").add_field("field", 2),
+            LineProtocol::new("measurement")
+                .add_field("field", 3)
+                .with_timestamp(1i64),
+            LineProtocol::new("measurement")
+                .add_field("field", 4)
+                .with_timestamp(2i64),
+        ];
+
+        assert!(is_timestamp_monotonic(&points));
+        assert_eq!(assert_monotonic(&points), Ok(()));
+    }
+
+    #[test]
+    fn test_batch_not_monotonic_reports_index() {
+        let points = vec![
+            LineProtocol::new("measurement")
+                .add_field("field", 1)
+                .with_timestamp(2i64),
+            LineProtocol::new("measurement").add_field("field", 2),
+            LineProtocol::new("measurement")
+                .add_field("field", 3)
+                .with_timestamp(1i64),
+            LineProtocol::new("measurement")
+                .add_field("field", 4)
+                .with_timestamp(0i64),
+        ];
+
+        assert!(!is_timestamp_monotonic(&points));
+        assert_eq!(assert_monotonic(&points), Err(2));
+    }
+
+    #[test]
+    fn test_batch_infer_precision() {
+        let points = vec![
+            LineProtocol::new("measurement")
+                .add_field("field", 1)
+                .with_timestamp(1729270461i64),
+            LineProtocol::new("measurement").add_field("field", 2),
+            LineProtocol::new("measurement")
+                .add_field("field", 3)
+                .with_timestamp(1729270522i64),
+        ];
+        assert_eq!(
+            infer_batch_precision(&points),
+            Some(TimestampPrecision::Seconds)
+        );
+    }
+
+    #[test]
+    fn test_batch_infer_precision_disagree_is_none() {
+        let points = vec![
+            LineProtocol::new("measurement")
+                .add_field("field", 1)
+                .with_timestamp(1729270461i64),
+            LineProtocol::new("measurement")
+                .add_field("field", 2)
+                .with_timestamp(1729270461612452700i64),
+        ];
+        assert_eq!(infer_batch_precision(&points), None);
+
+        let points = vec![LineProtocol::new("measurement").add_field("field", 1)];
+        assert_eq!(infer_batch_precision(&points), None);
+    }
+
+    #[test]
+    fn test_batch_summary() {
+        let points = vec![
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.5)
+                .add_field("cores", 8)
+                .with_timestamp(20i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.6)
+                .with_timestamp(10i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "b")
+                .add_field("usage", 0.7),
+            LineProtocol::new("mem")
+                .add_tag("host", "a")
+                .add_field("used", 1024)
+                .with_timestamp(30i64),
+        ];
+
+        let expected = BatchSummary {
+            points: 4,
+            series: 3,
+            min_timestamp: Some(10),
+            max_timestamp: Some(30),
+            fields: 5,
+        };
+        assert_eq!(batch_summary(&points), expected);
+        assert_eq!(batch_summary(&[]), BatchSummary::default());
+    }
+
+    #[test]
+    fn test_batch_merge_within_window() {
+        let points = vec![
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.5)
+                .add_field("cores", 8)
+                .with_timestamp(100i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "b")
+                .add_field("usage", 0.9)
+                .with_timestamp(102i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.6)
+                .add_field("load", 1.5)
+                .with_timestamp(105i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.7)
+                .with_timestamp(120i64),
+        ];
+
+        let merged = merge_within_window(points, 10);
+        let lines = merged
+            .iter()
+            .map(|point| point.build().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                "cpu,host=a cores=8i,load=1.5,usage=0.6 100",
+                "cpu,host=b usage=0.9 102",
+                "cpu,host=a usage=0.7 120",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_merge_within_window_mixed_precision() {
+        let points = vec![
+            LineProtocol::new("cpu")
+                .add_field("usage", 0.5)
+                .with_timestamp_precision(1i64, TimestampPrecision::Seconds),
+            LineProtocol::new("cpu")
+                .add_field("load", 1.5)
+                .with_timestamp(1_000_000_005i64),
+            LineProtocol::new("cpu")
+                .add_field("cores", 8)
+                .with_timestamp(20i64),
+        ];
+
+        let merged = merge_within_window(points, 10);
+        let lines = merged
+            .iter()
+            .map(|point| point.build().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec!["cpu cores=8i 20", "cpu load=1.5,usage=0.5 1"]);
+    }
+
+    #[test]
+    fn test_batch_diff() {
+        let old = vec![
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.5)
+                .add_field("cores", 8)
+                .with_timestamp(10i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "b")
+                .add_field("usage", 0.9)
+                .with_timestamp(10i64),
+            LineProtocol::new("mem")
+                .add_tag("host", "a")
+                .add_field("used", 1024)
+                .with_timestamp(10i64),
+        ];
+        let new = vec![
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.7)
+                .add_field("load", 1.5)
+                .with_timestamp(20i64),
+            LineProtocol::new("cpu")
+                .add_tag("host", "c")
+                .add_field("usage", 0.1)
+                .with_timestamp(20i64),
+            // Only the timestamp changed
+            LineProtocol::new("mem")
+                .add_tag("host", "a")
+                .add_field("used", 1024)
+                .with_timestamp(20i64),
+        ];
+
+        let diff = diff_batches(&old, &new);
+        assert!(!diff.is_empty());
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].series_key(), "cpu,host=c");
+
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].series_key(), "cpu,host=b");
+
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed[0];
+        assert_eq!(changed.series, "cpu,host=a");
+        assert_eq!(
+            changed.fields_added,
+            HashMap::from([(FieldKey::from("load"), FieldValue::Float(1.5))])
+        );
+        assert_eq!(
+            changed.fields_removed,
+            HashMap::from([(FieldKey::from("cores"), FieldValue::Integer(8))])
+        );
+        assert_eq!(
+            changed.fields_changed,
+            HashMap::from([(
+                FieldKey::from("usage"),
+                (FieldValue::Float(0.5), FieldValue::Float(0.7))
+            )])
+        );
+
+        assert!(diff_batches(&old, &old).is_empty());
+
+        // A field which only changed type is a change
+        let old = LineProtocol::parse_lines("measurement field=\"true\" 1").unwrap();
+        let new = LineProtocol::parse_lines("measurement field=true 1").unwrap();
+
+        let diff = diff_batches(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed[0].fields_changed,
+            HashMap::from([(
+                FieldKey::from("field"),
+                (FieldValue::String("true".into()), FieldValue::Boolean(true))
+            )])
+        );
+    }
+
+    #[test]
+    fn test_batch_single_measurement() {
+        let points = vec![
+            LineProtocol::new("cpu").add_field("usage", 0.5),
+            LineProtocol::new("cpu")
+                .add_tag("host", "a")
+                .add_field("usage", 0.6),
+        ];
+        assert_eq!(
+            assert_single_measurement(&points),
+            Ok(&Measurement::from("cpu"))
+        );
+    }
+
+    #[test]
+    fn test_batch_mixed_measurements_is_err() {
+        let points = vec![
+            LineProtocol::new("cpu").add_field("usage", 0.5),
+            LineProtocol::new("mem").add_field("used", 1024),
+            LineProtocol::new("cpu").add_field("usage", 0.6),
+        ];
+
+        let error = assert_single_measurement(&points).unwrap_err();
+        assert_eq!(
+            error.measurements,
+            vec![Measurement::from("cpu"), Measurement::from("mem")]
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected a single measurement, found [cpu, mem]"
+        );
+
+        let error = assert_single_measurement(&[]).unwrap_err();
+        assert!(error.measurements.is_empty());
+    }
+
+    #[test]
+    fn test_batch_builder_on_build() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let bytes = Arc::new(AtomicUsize::new(0));
+
+        let batch = BatchBuilder::new()
+            .on_build({
+                let calls = calls.clone();
+                let bytes = bytes.clone();
+                move |line, len| {
+                    assert_eq!(line.len(), len);
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    bytes.fetch_add(len, Ordering::Relaxed);
+                }
+            })
+            .add_point(LineProtocol::new("cpu").add_field("usage", 0.5))
+            .add_point(LineProtocol::new("cpu").add_field("usage", 0.6))
+            .add_point(LineProtocol::new("mem").add_field("used", 1024));
+        assert_eq!(batch.len(), 3);
+
+        let built = batch.build().unwrap();
+        assert_eq!(built, "cpu usage=0.5\ncpu usage=0.6\nmem used=1024i");
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(bytes.load(Ordering::Relaxed), built.len() - 2);
+
+        // Without a callback the batch builds the same
+        let mut batch = BatchBuilder::new();
+        batch.add_point_ref(LineProtocol::new("cpu").add_field("usage", 0.5));
+        assert_eq!(batch.build().unwrap(), "cpu usage=0.5");
+    }
+
+    #[test]
+    fn test_batch_to_bytes() {
+        let points = vec![
+            LineProtocol::new("measurement").add_field("field", 1),
+            LineProtocol::new("measurement")
+                .add_field("field", 2)
+                .with_timestamp(1729270461612452700i64),
+        ];
+
+        assert_eq!(
+            batch_to_bytes(&points).unwrap(),
+            b"measurement field=1i\nmeasurement field=2i 1729270461612452700".to_vec()
+        );
+        assert!(batch_to_bytes(&[]).unwrap().is_empty());
+
+        let invalid = vec![points[0].clone(), LineProtocol::new("measurement")];
+        assert!(batch_to_bytes(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_batch_chunk_by_bytes() {
+        let points = (0..5)
+            .map(|i| LineProtocol::new("measurement").add_field("field", i))
+            .collect::<Vec<_>>();
+
+        // Each line is 20 bytes, two lines and a newline fit in 41 bytes
+        let chunks = chunk_by_bytes(&points, 41).unwrap();
+        assert_eq!(
+            chunks,
+            vec![
+                "measurement field=0i\nmeasurement field=1i",
+                "measurement field=2i\nmeasurement field=3i",
+                "measurement field=4i",
+            ]
+        );
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 41));
+
+        assert!(chunk_by_bytes(&[], 41).unwrap().is_empty());
+
+        assert!(matches!(
+            chunk_by_bytes(&points, 10),
+            Err(LineProtocolError::BuilderError(
+                BuilderError::LineTooLarge { bytes: 20, max: 10 }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,289 @@
+//! A compact binary encoding of a [LineProtocol], e.g., for caching parsed
+//! data points in a memory store without rebuilding and reparsing the line
+//! protocol string
+//!
+//! The encoding is not line protocol and is only meant to be decoded by
+//! [LineProtocol::from_binary] of the same version of this crate
+//!
+//! The methods are named `to_binary` and `from_binary` rather than `to_bytes`
+//! and `from_bytes`, as [LineProtocol::to_bytes] builds the line protocol
+//! string as bytes
+//!
+//! # Encoding
+//! All integers are little endian and strings are prefixed with their length
+//! as a u32
+//!
+//! ```text
+//! measurement      string
+//! tag set          u8 (0 = no tags, 1 = tags), u32 count, (string, string)*
+//! field set        u32 count, (string, u8 type, value)*
+//! timestamp        u8 (0 = no timestamp, 1 = timestamp), i64
+//! precision        u8
+//! ```
+
+use crate::{
+    element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
+    error::{DecodeError, Result},
+    precision::TimestampPrecision,
+    LineProtocol, Map,
+};
+
+const FLOAT: u8 = 0;
+const INTEGER: u8 = 1;
+const UINTEGER: u8 = 2;
+const STRING: u8 = 3;
+const BOOLEAN: u8 = 4;
+
+/// Appends values to the encoded buffer
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u32(&mut self, value: usize) {
+        // Strings and sets are never anywhere close to 4GB
+        self.bytes.extend((value as u32).to_le_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.u32(value.len());
+        self.bytes.extend(value.as_bytes());
+    }
+}
+
+/// Reads values from the encoded buffer
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, len: usize) -> std::result::Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> std::result::Result<[u8; N], DecodeError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> std::result::Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> std::result::Result<usize, DecodeError> {
+        Ok(u32::from_le_bytes(self.array()?) as usize)
+    }
+
+    fn string(&mut self) -> std::result::Result<String, DecodeError> {
+        let len = self.u32()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl LineProtocol {
+    /// Encode the data point into a compact binary representation
+    ///
+    /// The data point is not validated, use [LineProtocol::from_binary] to
+    /// decode it again
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+    ///
+    /// let bytes = line_protocol.to_binary();
+    /// let decoded = LineProtocol::from_binary(&bytes).unwrap();
+    /// ```
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut encoder = Encoder { bytes: Vec::new() };
+        encoder.string(&self.measurement.0);
+
+        match &self.tags {
+            Some(tags) => {
+                encoder.u8(1);
+                encoder.u32(tags.len());
+                for (key, value) in tags {
+                    encoder.string(&key.0);
+                    encoder.string(&value.0);
+                }
+            }
+            None => encoder.u8(0),
+        }
+
+        encoder.u32(self.fields.len());
+        for (key, value) in &self.fields {
+            encoder.string(&key.0);
+            match value {
+                FieldValue::Float(number) => {
+                    encoder.u8(FLOAT);
+                    encoder.bytes.extend(number.to_le_bytes());
+                }
+                FieldValue::Integer(number) => {
+                    encoder.u8(INTEGER);
+                    encoder.bytes.extend(number.to_le_bytes());
+                }
+                FieldValue::UInteger(number) => {
+                    encoder.u8(UINTEGER);
+                    encoder.bytes.extend(number.to_le_bytes());
+                }
+                FieldValue::String(string) => {
+                    encoder.u8(STRING);
+                    encoder.string(string);
+                }
+                FieldValue::Boolean(boolean) => {
+                    encoder.u8(BOOLEAN);
+                    encoder.u8(u8::from(*boolean));
+                }
+            }
+        }
+
+        match self.timestamp {
+            Some(timestamp) => {
+                encoder.u8(1);
+                encoder.bytes.extend(timestamp.to_le_bytes());
+            }
+            None => encoder.u8(0),
+        }
+
+        encoder.u8(match self.precision {
+            TimestampPrecision::Nanoseconds => 0,
+            TimestampPrecision::Microseconds => 1,
+            TimestampPrecision::Milliseconds => 2,
+            TimestampPrecision::Seconds => 3,
+        });
+
+        encoder.bytes
+    }
+
+    /// Decode a data point encoded with [LineProtocol::to_binary]
+    ///
+    /// # Example
+    /// ```rust
+    /// let bytes = line_protocol.to_binary();
+    /// let decoded = LineProtocol::from_binary(&bytes).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `bytes` - A binary encoded data point
+    pub fn from_binary(bytes: &[u8]) -> Result<LineProtocol> {
+        let mut decoder = Decoder { bytes };
+        let measurement = Measurement(decoder.string()?);
+
+        let tags = match decoder.u8()? {
+            0 => None,
+            1 => {
+                let count = decoder.u32()?;
+                let mut tags = Map::new();
+                for _ in 0..count {
+                    let key = TagKey(decoder.string()?);
+                    let value = TagValue(decoder.string()?);
+                    tags.insert(key, value);
+                }
+                Some(tags)
+            }
+            other => return Err(DecodeError::InvalidMarker(other).into()),
+        };
+
+        let count = decoder.u32()?;
+        let mut fields = Map::new();
+        for _ in 0..count {
+            let key = FieldKey(decoder.string()?);
+            let value = match decoder.u8()? {
+                FLOAT => FieldValue::Float(f64::from_le_bytes(decoder.array()?)),
+                INTEGER => FieldValue::Integer(i64::from_le_bytes(decoder.array()?)),
+                UINTEGER => FieldValue::UInteger(u64::from_le_bytes(decoder.array()?)),
+                STRING => FieldValue::String(decoder.string()?),
+                BOOLEAN => FieldValue::Boolean(decoder.u8()? != 0),
+                other => return Err(DecodeError::InvalidMarker(other).into()),
+            };
+            fields.insert(key, value);
+        }
+
+        let timestamp = match decoder.u8()? {
+            0 => None,
+            1 => Some(i64::from_le_bytes(decoder.array()?)),
+            other => return Err(DecodeError::InvalidMarker(other).into()),
+        };
+
+        let precision = match decoder.u8()? {
+            0 => TimestampPrecision::Nanoseconds,
+            1 => TimestampPrecision::Microseconds,
+            2 => TimestampPrecision::Milliseconds,
+            3 => TimestampPrecision::Seconds,
+            other => return Err(DecodeError::InvalidMarker(other).into()),
+        };
+
+        if !decoder.bytes.is_empty() {
+            return Err(DecodeError::TrailingBytes(decoder.bytes.len()).into());
+        }
+
+        Ok(LineProtocol {
+            measurement,
+            tags,
+            fields,
+            timestamp,
+            precision,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binary_round_trip() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag1", "value")
+            .add_tag("tag2", "välue with spaces")
+            .add_field("float", 0.5)
+            .add_field("integer", -10)
+            .add_field("uinteger", u64::MAX)
+            .add_field("string", "hello \"world\"")
+            .add_field("boolean", true)
+            .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+
+        let bytes = line_protocol.to_binary();
+        let decoded = LineProtocol::from_binary(&bytes).unwrap();
+
+        assert_eq!(decoded.build().unwrap(), line_protocol.build().unwrap());
+        assert_eq!(decoded.fields, line_protocol.fields);
+        assert_eq!(decoded.precision, TimestampPrecision::Seconds);
+
+        // A data point without tags and timestamp
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        let decoded = LineProtocol::from_binary(&line_protocol.to_binary()).unwrap();
+        assert!(decoded.tags.is_none());
+        assert!(decoded.timestamp.is_none());
+        assert_eq!(decoded.build().unwrap(), line_protocol.build().unwrap());
+    }
+
+    #[test]
+    fn test_binary_invalid_is_err() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64);
+        let bytes = line_protocol.to_binary();
+
+        let result = LineProtocol::from_binary(&bytes[..bytes.len() - 1]);
+        assert!(result.is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(LineProtocol::from_binary(&trailing).is_err());
+
+        assert!(LineProtocol::from_binary(&[]).is_err());
+    }
+}
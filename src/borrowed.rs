@@ -0,0 +1,440 @@
+//! A zero-copy variant of the parser
+//!
+//! [LineProtocol::parse_line_ref] parses a line into a [LineProtocolRef] whose
+//! elements borrow from the input line. An element is only allocated if it
+//! contains escaped characters which have to be unescaped. This is useful when
+//! only parts of a line are inspected, e.g., the tags, and the rest is
+//! discarded
+//!
+//! Use [LineProtocolRef::to_owned] to convert it into a regular
+//! [LineProtocol]
+
+use std::borrow::Cow;
+
+use crate::{
+    element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
+    error::{ParseError, Result},
+    precision::TimestampPrecision,
+    traits::{Convert, Format},
+    LineProtocol, Map,
+};
+
+/// UTF-8 byte order mark
+const BOM: char = '\u{FEFF}';
+
+/// A field value borrowing from the parsed line, see [FieldValue]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValueRef<'a> {
+    /// Represent a floating point number field value
+    Float(f64),
+
+    /// Represent a signed integer number field value
+    Integer(i64),
+
+    /// Represent an unsigned integer number field value
+    UInteger(u64),
+
+    /// Represent an unescaped string field value
+    String(Cow<'a, str>),
+
+    /// Represent a boolean field value
+    Boolean(bool),
+}
+
+impl FieldValueRef<'_> {
+    /// Convert into an owned [FieldValue]
+    pub fn to_field_value(&self) -> FieldValue {
+        match self {
+            FieldValueRef::Float(number) => FieldValue::Float(*number),
+            FieldValueRef::Integer(number) => FieldValue::Integer(*number),
+            FieldValueRef::UInteger(number) => FieldValue::UInteger(*number),
+            FieldValueRef::String(string) => FieldValue::String(string.to_string()),
+            FieldValueRef::Boolean(boolean) => FieldValue::Boolean(*boolean),
+        }
+    }
+}
+
+/// A parsed data point borrowing from the parsed line
+///
+/// All keys and values are unescaped. The tags and fields are kept in the
+/// order they appear in the line
+#[derive(Debug, Clone)]
+pub struct LineProtocolRef<'a> {
+    measurement: Cow<'a, str>,
+    tags: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    fields: Vec<(Cow<'a, str>, FieldValueRef<'a>)>,
+    timestamp: Option<i64>,
+}
+
+impl<'a> LineProtocolRef<'a> {
+    /// Get the measurement
+    pub fn get_measurement(&self) -> &str {
+        &self.measurement
+    }
+
+    /// Get the tag value associated with the provided tag key
+    ///
+    /// # Args
+    /// * `key` - An unescaped tag key
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_key, _)| tag_key == key)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Get the field value associated with the provided field key
+    ///
+    /// # Args
+    /// * `key` - An unescaped field key
+    pub fn get_field(&self, key: &str) -> Option<&FieldValueRef<'a>> {
+        self.fields
+            .iter()
+            .find(|(field_key, _)| field_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Get the timestamp
+    pub fn get_timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    /// Iterate over the tag set in the order it appears in the line
+    pub fn tags_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+
+    /// Iterate over the field set in the order it appears in the line
+    pub fn fields_iter(&self) -> impl Iterator<Item = (&str, &FieldValueRef<'a>)> {
+        self.fields.iter().map(|(key, value)| (key.as_ref(), value))
+    }
+
+    /// Get the amount of tags
+    pub fn tag_count(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Get the amount of fields
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Convert into a regular [LineProtocol] owning all of its elements
+    ///
+    /// # Example
+    /// ```rust
+    /// let parsed_line = LineProtocol::parse_line_ref(line).unwrap();
+    /// let line_protocol = parsed_line.to_owned();
+    /// ```
+    pub fn to_owned(&self) -> LineProtocol {
+        let tags = (!self.tags.is_empty()).then(|| {
+            self.tags
+                .iter()
+                .map(|(key, value)| (TagKey::from(key.as_ref()), TagValue::from(value.as_ref())))
+                .collect::<Map<_, _>>()
+        });
+
+        let fields = self
+            .fields
+            .iter()
+            .map(|(key, value)| (FieldKey::from(key.as_ref()), value.to_field_value()))
+            .collect();
+
+        LineProtocol {
+            measurement: Measurement::from(self.measurement.as_ref()),
+            tags,
+            fields,
+            timestamp: self.timestamp,
+            precision: TimestampPrecision::Nanoseconds,
+        }
+    }
+}
+
+/// Unescape an element, only allocating if it contains an escaped character
+fn unescape<'a, T>(raw: &'a str, element: fn(String) -> T, inner: fn(T) -> String) -> Cow<'a, str>
+where
+    T: Format,
+{
+    match raw.contains('\\') {
+        true => Cow::Owned(inner(element(raw.to_string()).unescape())),
+        false => Cow::Borrowed(raw),
+    }
+}
+
+/// Split a part from the rest of the line at the first space which is not
+/// escaped nor in a quote
+fn split_part(line: &str) -> (&str, &str) {
+    let mut in_quote = false;
+    let mut is_escaped = false;
+
+    for (index, char) in line.char_indices() {
+        if char == '\\' && !is_escaped {
+            is_escaped = true;
+            continue;
+        }
+
+        if char == '"' && !is_escaped {
+            in_quote = !in_quote;
+        } else if char == ' ' && !is_escaped && !in_quote {
+            return (line[..index].trim(), &line[index + 1..]);
+        }
+        is_escaped = false;
+    }
+
+    (line.trim(), "")
+}
+
+/// Split a set into its words at each equals sign and comma which is not
+/// escaped nor in a quote
+fn split_set(set: &str) -> Result<Vec<(&str, &str)>> {
+    let mut in_quote = false;
+    let mut is_escaped = false;
+
+    let mut start = 0;
+    let mut words = Vec::new();
+    for (index, char) in set.char_indices() {
+        if char == '\\' && !is_escaped {
+            is_escaped = true;
+            continue;
+        }
+
+        if char == '"' && !is_escaped {
+            in_quote = !in_quote;
+        } else if (char == '=' || char == ',') && !is_escaped && !in_quote {
+            words.push(&set[start..index]);
+            start = index + 1;
+        }
+        is_escaped = false;
+    }
+    words.push(&set[start..]);
+
+    if words.len() % 2 != 0 || words.iter().any(|word| word.is_empty()) {
+        return Err(ParseError::InvalidSet("set contains uneven amount of values".into()).into());
+    }
+
+    Ok(words
+        .chunks_exact(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect())
+}
+
+/// Parse a raw field value, only allocating for a string containing escaped
+/// characters
+fn parse_field_value(raw: &str) -> Result<FieldValueRef<'_>> {
+    if raw.len() >= 2 && raw.starts_with('"') && raw.ends_with('"') {
+        let unquoted = &raw[1..raw.len() - 1];
+        let string = match unquoted.contains('\\') {
+            true => match FieldValue::String(raw.to_string()).unescape() {
+                FieldValue::String(string) => Cow::Owned(string),
+                _ => unreachable!(),
+            },
+            false => Cow::Borrowed(unquoted),
+        };
+        return Ok(FieldValueRef::String(string));
+    }
+
+    let value = FieldValue::parse_from(raw).map_err(ParseError::from_value_error)?;
+    Ok(match value.unescape() {
+        FieldValue::Float(number) => FieldValueRef::Float(number),
+        FieldValue::Integer(number) => FieldValueRef::Integer(number),
+        FieldValue::UInteger(number) => FieldValueRef::UInteger(number),
+        FieldValue::String(string) => FieldValueRef::String(Cow::Owned(string)),
+        FieldValue::Boolean(boolean) => FieldValueRef::Boolean(boolean),
+    })
+}
+
+impl LineProtocol {
+    /// Parse a single line protocol line into a [LineProtocolRef] borrowing
+    /// from the line
+    ///
+    /// The line is parsed the same way as [LineProtocol::parse_line]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = "measurement,host=a field=true 1729270461612452700";
+    /// let parsed_line = LineProtocol::parse_line_ref(line).unwrap();
+    ///
+    /// let host = parsed_line.get_tag("host");
+    /// ```
+    ///
+    /// # Args
+    /// * `line` - A InfluxDB line protocol line
+    pub fn parse_line_ref(line: &str) -> Result<LineProtocolRef<'_>> {
+        let line = line.strip_prefix(BOM).unwrap_or(line).trim();
+
+        if line.starts_with('#') {
+            return Err(ParseError::CommentLine.into());
+        }
+
+        if line.is_empty() {
+            return Err(ParseError::EmptyLine.into());
+        }
+
+        if line.contains('\r') {
+            return Err(ParseError::StrayCarriageReturn.into());
+        }
+
+        let (identifiers, rest) = split_part(line);
+        let (field_set, timestamp) = split_part(rest);
+
+        // Split the measurement from the tag set at the first unescaped comma
+        let mut is_escaped = false;
+        let split = identifiers.char_indices().find(|(_, char)| {
+            let is_separator = *char == ',' && !is_escaped;
+            is_escaped = *char == '\\' && !is_escaped;
+            is_separator
+        });
+        let (measurement, tag_set) = match split {
+            Some((index, _)) => (&identifiers[..index], &identifiers[index + 1..]),
+            None => (identifiers, ""),
+        };
+
+        if measurement.is_empty() {
+            return Err(ParseError::MissingMeasurement.into());
+        }
+        let measurement = unescape(measurement, Measurement, |m| m.0);
+
+        let mut tags: Vec<(Cow<str>, Cow<str>)> = Vec::new();
+        if !tag_set.is_empty() {
+            for (key, value) in split_set(tag_set)? {
+                let key = unescape(key, TagKey, |k| k.0);
+                if tags.iter().any(|(tag_key, _)| *tag_key == key) {
+                    return Err(ParseError::DuplicateKey(key.to_string()).into());
+                }
+                tags.push((key, unescape(value, TagValue, |v| v.0)));
+            }
+        }
+
+        // A field set always contains atleast one key value pair
+        if field_set.is_empty() || !field_set.contains('=') {
+            return Err(ParseError::MissingFields.into());
+        }
+
+        let mut fields: Vec<(Cow<str>, FieldValueRef)> = Vec::new();
+        for (key, value) in split_set(field_set)? {
+            let key = unescape(key, FieldKey, |k| k.0);
+            if fields.iter().any(|(field_key, _)| *field_key == key) {
+                return Err(ParseError::DuplicateKey(key.to_string()).into());
+            }
+            fields.push((key, parse_field_value(value)?));
+        }
+
+        let timestamp = match !timestamp.is_empty() {
+            true => {
+                let (timestamp, trailing) = timestamp.split_once(' ').unwrap_or((timestamp, ""));
+                let timestamp = match timestamp.parse::<i64>() {
+                    Ok(timestamp) => timestamp,
+                    Err(_) => return Err(ParseError::InvalidTimestamp.into()),
+                };
+
+                if !trailing.is_empty() {
+                    return Err(ParseError::TrailingData(trailing.to_string()).into());
+                }
+                Some(timestamp)
+            }
+            false => None,
+        };
+
+        Ok(LineProtocolRef {
+            measurement,
+            tags,
+            fields,
+            timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::LineProtocolError;
+
+    #[test]
+    fn test_borrowed_parse_line_ref() {
+        let line = "measurement,tag1=value,tag2=value\\ 2 \
+                    string=\"hello\",integer=10i,float=0.5,boolean=true 1729270461612452700";
+        let parsed = LineProtocol::parse_line_ref(line).unwrap();
+
+        assert_eq!(parsed.get_measurement(), "measurement");
+        assert_eq!(parsed.get_tag("tag1"), Some("value"));
+        assert_eq!(parsed.get_tag("tag2"), Some("value 2"));
+        assert_eq!(parsed.get_tag("missing"), None);
+        assert_eq!(parsed.tag_count(), 2);
+        assert_eq!(parsed.field_count(), 4);
+        assert_eq!(parsed.get_timestamp(), Some(1729270461612452700));
+
+        assert_eq!(
+            parsed.get_field("string"),
+            Some(&FieldValueRef::String(Cow::Borrowed("hello")))
+        );
+        assert_eq!(
+            parsed.get_field("integer"),
+            Some(&FieldValueRef::Integer(10))
+        );
+
+        // Unescaped elements borrow from the line, escaped ones are allocated
+        assert!(matches!(parsed.measurement, Cow::Borrowed(_)));
+        assert!(matches!(parsed.tags[1].1, Cow::Owned(_)));
+
+        let owned = parsed.to_owned();
+        let expected = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(owned.build().unwrap(), expected.build().unwrap());
+        assert_eq!(owned.fields, expected.fields);
+    }
+
+    #[test]
+    fn test_borrowed_matches_parse_line() {
+        let lines = [
+            "measurement field=\"a=b,c d\"",
+            "measurement field=\"with \\\"quote\\\"\" 1",
+            "measurement,tag=a\\,b field=1u",
+            "mea\\ sure\\,ment,t\\=k=v field\\ key=-1i",
+            "measurement field=\"C:\\\\path\\\\\"",
+        ];
+
+        for line in lines {
+            let borrowed = LineProtocol::parse_line_ref(line).unwrap().to_owned();
+            let owned = LineProtocol::parse_line(line).unwrap();
+            assert_eq!(borrowed.build().unwrap(), owned.build().unwrap(), "{line}");
+            assert_eq!(borrowed.fields, owned.fields, "{line}");
+        }
+
+        // Both reject an empty key or value
+        let invalid = ["m,a=,b=c f=1i", "m,=a f=1i", "m f=,g=1i"];
+
+        for line in invalid {
+            assert!(LineProtocol::parse_line_ref(line).is_err(), "{line}");
+            assert!(LineProtocol::parse_line(line).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_borrowed_invalid_is_err() {
+        let cases = [
+            ("# comment", ParseError::CommentLine),
+            ("", ParseError::EmptyLine),
+            (",tag=value field=1i", ParseError::MissingMeasurement),
+            ("measurement,tag=value 12345", ParseError::MissingFields),
+            (
+                "measurement field=1i timestamp",
+                ParseError::InvalidTimestamp,
+            ),
+            (
+                "measurement,tag=a,tag=b field=1i",
+                ParseError::DuplicateKey("tag".to_string()),
+            ),
+        ];
+
+        for (line, expected) in cases {
+            let error = LineProtocol::parse_line_ref(line).unwrap_err();
+            let LineProtocolError::ParserError(error) = error else {
+                panic!("{line}: expected a parser error");
+            };
+            assert_eq!(error.to_string(), expected.to_string(), "{line}");
+        }
+
+        assert!(LineProtocol::parse_line_ref("measurement field=").is_err());
+    }
+}
@@ -6,18 +6,457 @@
 //! datapoint. When you are finished call [LineProtocol::build] to convert the
 //! struct into a valid line protocol string
 
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    io,
+    marker::PhantomData,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::{
-    element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
-    error::BuilderError,
+    element::{FieldKey, FieldType, FieldValue, Measurement, TagKey, TagValue},
+    error::{BoxError, BuilderError, LineProtocolError},
+    map_remove,
+    precision::TimestampPrecision,
     traits::Format,
-    LineProtocol,
+    LineProtocol, Map,
 };
 
 use crate::error::Result;
 
+/// The field key used by [LineProtocol::tombstone]
+pub const TOMBSTONE_FIELD: &str = "_deleted";
+
+/// The validated and escaped parts of a data point, joined together by
+/// [LineProtocol::build_with] and [LineProtocol::write_to]
+struct FormattedLine<'a> {
+    measurement: Cow<'a, str>,
+    tags: Option<Vec<String>>,
+    fields: Vec<String>,
+    timestamp: Option<i64>,
+}
+
+impl FormattedLine<'_> {
+    /// Writes the joined parts without a trailing newline
+    fn write<W>(self, w: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        w.write_all(self.measurement.as_bytes())?;
+        if let Some(tags) = self.tags {
+            w.write_all(b",")?;
+            w.write_all(tags.join(",").as_bytes())?;
+        }
+
+        w.write_all(b" ")?;
+        w.write_all(self.fields.join(",").as_bytes())?;
+
+        if let Some(timestamp) = self.timestamp {
+            write!(w, " {timestamp}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Appends the joined parts to a string without a trailing newline
+    fn push_to(self, line: &mut String) {
+        line.push_str(&self.measurement);
+        if let Some(tags) = self.tags {
+            line.push(',');
+            line.push_str(&tags.join(","));
+        }
+
+        line.push(' ');
+        line.push_str(&self.fields.join(","));
+
+        if let Some(timestamp) = self.timestamp {
+            line.push(' ');
+            line.push_str(&timestamp.to_string());
+        }
+    }
+}
+
+/// Caps on the size of a data point, checked when building with
+/// [LineProtocol::build_with_limits] or [BuildOptions::limits]
+///
+/// Catches pathological data points, e.g., a runaway tag set, before they
+/// reach the write API. All limits are disabled by default
+///
+/// # Example
+/// ```rust
+/// let limits = BuildLimits::new().max_tags(Some(32)).max_key_len(Some(256));
+/// let line = line_protocol.build_with_limits(&limits).unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildLimits {
+    max_tags: Option<usize>,
+    max_fields: Option<usize>,
+    max_key_len: Option<usize>,
+    max_line_bytes: Option<usize>,
+}
+
+impl BuildLimits {
+    /// Create a new set of limits with all limits disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum amount of tags
+    ///
+    /// # Args
+    /// * `max` - The maximum amount of tags, or `None` to disable the limit
+    pub fn max_tags(mut self, max: Option<usize>) -> Self {
+        self.max_tags = max;
+        self
+    }
+
+    /// Set the maximum amount of fields
+    ///
+    /// # Args
+    /// * `max` - The maximum amount of fields, or `None` to disable the limit
+    pub fn max_fields(mut self, max: Option<usize>) -> Self {
+        self.max_fields = max;
+        self
+    }
+
+    /// Set the maximum length in bytes of the measurement name, tag keys, and
+    /// field keys before escaping
+    ///
+    /// # Args
+    /// * `max` - The maximum amount of bytes, or `None` to disable the limit
+    pub fn max_key_len(mut self, max: Option<usize>) -> Self {
+        self.max_key_len = max;
+        self
+    }
+
+    /// Set the maximum length in bytes of the built line
+    ///
+    /// # Args
+    /// * `max` - The maximum amount of bytes, or `None` to disable the limit
+    pub fn max_line_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_line_bytes = max;
+        self
+    }
+
+    /// Check the length of a measurement name or key
+    fn check_key(&self, kind: &str, key: &str) -> Result<()> {
+        match self.max_key_len {
+            Some(max) if key.len() > max => Err(BuilderError::KeyTooLong {
+                key: format!("{kind} {key}"),
+                len: key.len(),
+                max,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// How float field values are written, see [BuildOptions::float_format]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest representation which parses back to the same value, e.g.,
+    /// `10.0` is written as `10` and `0.1 + 0.2` as `0.30000000000000004`
+    #[default]
+    Shortest,
+
+    /// A fixed amount of decimals, e.g., `Fixed(3)` writes `10.0` as `10.000`.
+    /// Values are rounded to the amount of decimals, meaning precision can be
+    /// lost
+    Fixed(usize),
+
+    /// Scientific notation, e.g., `1500.0` is written as `1.5e3`
+    Scientific,
+}
+
+impl FloatFormat {
+    /// Format a float according to the format
+    fn format(&self, number: f64) -> String {
+        match self {
+            FloatFormat::Shortest => format!("{number}"),
+            FloatFormat::Fixed(decimals) => format!("{number:.decimals$}"),
+            FloatFormat::Scientific => format!("{number:e}"),
+        }
+    }
+}
+
+/// Options used to change how a data point is built
+///
+/// By default the options follow the line protocol specification, meaning
+/// [LineProtocol::build] and [LineProtocol::build_with] using the default
+/// options are equal
+///
+/// # Example
+/// ```rust
+/// let options = BuildOptions::new().max_total_tag_bytes(Some(1024));
+/// let line = line_protocol.build_with(&options).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    max_total_tag_bytes: Option<usize>,
+    timestamp: Option<i64>,
+    numeric_suffixes: bool,
+    allow_reserved_names: bool,
+    preserve_order: bool,
+    limits: BuildLimits,
+    reject_blank_strings: bool,
+    float_format: FloatFormat,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            max_total_tag_bytes: None,
+            timestamp: None,
+            numeric_suffixes: true,
+            allow_reserved_names: false,
+            preserve_order: false,
+            limits: BuildLimits::default(),
+            reject_blank_strings: false,
+            float_format: FloatFormat::Shortest,
+        }
+    }
+}
+
+impl BuildOptions {
+    /// Create a new set of build options with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum combined length in bytes of all tag keys and values
+    ///
+    /// Guards against accidentally creating series with very large tag sets.
+    /// Disabled by default
+    ///
+    /// # Args
+    /// * `max` - The maximum amount of bytes, or `None` to disable the limit
+    pub fn max_total_tag_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_total_tag_bytes = max;
+        self
+    }
+
+    /// Set a timestamp which overrides the timestamp of the data point
+    ///
+    /// Useful when the stored timestamp should be replaced by a batch-level
+    /// write time. Disabled by default
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp, or `None` to use the data point's own
+    pub fn timestamp(mut self, timestamp: Option<i64>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Set whether integer fields are suffixed with `i` and unsigned integer
+    /// fields with `u`, e.g., `10i` and `10u`
+    ///
+    /// Disabling the suffix renders integers like floats, e.g., `10`. This is
+    /// **not** valid line protocol for InfluxDB as the integers are written as
+    /// floats, it is only meant for near-compatible consumers which can't
+    /// handle the suffix. Enabled by default
+    ///
+    /// # Args
+    /// * `enabled` - Whether integer fields are suffixed
+    pub fn numeric_suffixes(mut self, enabled: bool) -> Self {
+        self.numeric_suffixes = enabled;
+        self
+    }
+
+    /// Set whether the measurement name, tag keys, and field keys may start
+    /// with an `_` (underscore)
+    ///
+    /// InfluxDB reserves names starting with an underscore for its own use,
+    /// only enable this for conventions which knowingly rely on them, e.g.,
+    /// [LineProtocol::tombstone]. Disabled by default
+    ///
+    /// # Args
+    /// * `enabled` - Whether reserved names are allowed
+    pub fn allow_reserved_names(mut self, enabled: bool) -> Self {
+        self.allow_reserved_names = enabled;
+        self
+    }
+
+    /// Set whether the tags and fields are emitted in the order they are
+    /// stored in instead of sorted by key
+    ///
+    /// Only meaningful with the `indexmap` feature enabled, which stores the
+    /// tags and fields in insertion order. Without it the order is arbitrary.
+    /// The preserved order is meant for readability, InfluxDB
+    /// [recommends](https://docs.influxdata.com/influxdb/v2/write-data/best-practices/optimize-writes/#sort-tags-by-key)
+    /// sorted tags for write performance. Disabled by default
+    ///
+    /// # Args
+    /// * `enabled` - Whether the stored order is preserved
+    pub fn preserve_order(mut self, enabled: bool) -> Self {
+        self.preserve_order = enabled;
+        self
+    }
+
+    /// Set the size limits the data point must stay within, see
+    /// [BuildLimits]. No limits are enforced by default
+    ///
+    /// # Args
+    /// * `limits` - The limits enforced when building
+    pub fn limits(mut self, limits: BuildLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set whether string field values only containing whitespace, e.g.,
+    /// `"   "`, are rejected like empty string field values
+    ///
+    /// InfluxDB stores such values as is, but they are usually the result of a
+    /// bug in the data, e.g., an unset fixed width column. Disabled by default
+    /// to keep accepting existing data
+    ///
+    /// # Args
+    /// * `enabled` - Whether whitespace-only string field values are rejected
+    pub fn reject_blank_strings(mut self, enabled: bool) -> Self {
+        self.reject_blank_strings = enabled;
+        self
+    }
+
+    /// Set how float field values are written, e.g., with a fixed amount of
+    /// decimals for reproducible output. [FloatFormat::Shortest] by default
+    ///
+    /// # Args
+    /// * `format` - The format of float field values
+    pub fn float_format(mut self, format: FloatFormat) -> Self {
+        self.float_format = format;
+        self
+    }
+}
+
+/// Decides which value is kept when merging two data points sharing a key,
+/// see [LineProtocol::merge_with_strategy]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the value of the data point being merged into
+    KeepSelf,
+
+    /// Keep the value of the data point being merged from
+    #[default]
+    KeepOther,
+}
+
+/// Marks a [LineProtocolBuilder] without any fields
+#[derive(Debug, Clone, Copy)]
+pub struct NoFields;
+
+/// Marks a [LineProtocolBuilder] with atleast one field
+#[derive(Debug, Clone, Copy)]
+pub struct HasFields;
+
+/// A builder which only allows building a data point once atleast one field
+/// is added, checked at compile time
+///
+/// Create one with [LineProtocol::builder]. The remaining validation, e.g.,
+/// of the measurement name, is still done when building
+///
+/// # Example
+/// ```rust
+/// let line = LineProtocol::builder("measurement")
+///     .add_tag("tag", "value")
+///     .add_field("field", "value")
+///     .build()
+///     .unwrap();
+///
+/// // Does not compile, no field was added
+/// let line = LineProtocol::builder("measurement").build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineProtocolBuilder<S> {
+    line_protocol: LineProtocol,
+    state: PhantomData<S>,
+}
+
+impl<S> LineProtocolBuilder<S> {
+    /// Add or update a tag key-value pair, see [LineProtocol::add_tag]
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key
+    /// * `value` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag value
+    pub fn add_tag<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        self.line_protocol.add_tag_ref(key, value);
+        self
+    }
+
+    /// Add or update a field key-value pair, see [LineProtocol::add_field]
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key
+    /// * `value` - A field value
+    pub fn add_field<K, V>(mut self, key: K, value: V) -> LineProtocolBuilder<HasFields>
+    where
+        K: Into<FieldKey>,
+        V: Into<FieldValue>,
+    {
+        self.line_protocol.add_field_ref(key, value);
+        LineProtocolBuilder {
+            line_protocol: self.line_protocol,
+            state: PhantomData,
+        }
+    }
+
+    /// Set the timestamp for the data point, see [LineProtocol::with_timestamp]
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp in nanoseconds
+    pub fn with_timestamp<T>(mut self, timestamp: T) -> Self
+    where
+        T: Into<i64>,
+    {
+        self.line_protocol.with_timestamp_ref(timestamp);
+        self
+    }
+}
+
+impl LineProtocolBuilder<HasFields> {
+    /// Builds an InfluxDB v2 data point, see [LineProtocol::build]
+    pub fn build(&self) -> Result<String> {
+        self.line_protocol.build()
+    }
+
+    /// Get the built [LineProtocol], e.g., to modify it further
+    pub fn into_inner(self) -> LineProtocol {
+        self.line_protocol
+    }
+}
+
 impl LineProtocol {
+    /// Create a [LineProtocolBuilder] which requires atleast one field to be
+    /// added before the data point can be built
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = LineProtocol::builder("measurement")
+    ///     .add_field("field", "value")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `measurement` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#measurement)
+    ///   measurement name
+    pub fn builder<T>(measurement: T) -> LineProtocolBuilder<NoFields>
+    where
+        T: Into<Measurement>,
+    {
+        LineProtocolBuilder {
+            line_protocol: LineProtocol::new(measurement),
+            state: PhantomData,
+        }
+    }
+
     /// Create a new [LineProtocol] for building a single data point
     ///
     /// # Args
@@ -30,9 +469,164 @@ impl LineProtocol {
         Self {
             measurement: measurement.into(),
             tags: None,
-            fields: HashMap::new(),
+            fields: Map::new(),
             timestamp: None,
+            precision: TimestampPrecision::Nanoseconds,
+        }
+    }
+
+    /// Create a new [LineProtocol] for building a single data point where the
+    /// measurement name is derived from any type implementing [Display]
+    ///
+    /// # Example
+    /// ```rust
+    /// enum Sensor {
+    ///     Temperature,
+    /// }
+    ///
+    /// impl Display for Sensor { ... }
+    ///
+    /// let line_protocol = LineProtocol::new_from(Sensor::Temperature);
+    /// ```
+    ///
+    /// # Args
+    /// * `measurement` - A type which displays as a [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#measurement)
+    ///   measurement name
+    pub fn new_from<T>(measurement: T) -> Self
+    where
+        T: Display,
+    {
+        LineProtocol::new(measurement.to_string())
+    }
+
+    /// Create a new [LineProtocol] from columnar input where the field keys
+    /// and field values are stored in two parallel arrays
+    ///
+    /// The field columns are zipped together, meaning the key at index `n` is
+    /// paired with the value at index `n`. An error is returned if the columns
+    /// are not of equal length
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::from_columns(
+    ///     "measurement",
+    ///     vec![("tag", "value")],
+    ///     &["field1", "field2"],
+    ///     &[FieldValue::Float(0.5), FieldValue::Boolean(true)],
+    ///     Some(1729270461612452700),
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `measurement` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#measurement)
+    ///   measurement name
+    /// * `tag_pairs` - Tag key-value pairs
+    /// * `field_keys` - The field key column
+    /// * `field_values` - The field value column
+    /// * `timestamp` - An optional unix timestamp
+    pub fn from_columns<T, I, K, V>(
+        measurement: T,
+        tag_pairs: I,
+        field_keys: &[&str],
+        field_values: &[FieldValue],
+        timestamp: Option<i64>,
+    ) -> Result<Self>
+    where
+        T: Into<Measurement>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        if field_keys.len() != field_values.len() {
+            return Err(BuilderError::ColumnLengthMismatch {
+                keys: field_keys.len(),
+                values: field_values.len(),
+            }
+            .into());
+        }
+
+        let mut line_protocol = LineProtocol::new(measurement);
+        for (key, value) in tag_pairs {
+            line_protocol.add_tag_ref(key, value);
         }
+
+        for (key, value) in field_keys.iter().zip(field_values) {
+            line_protocol.add_field_ref(*key, value.clone());
+        }
+
+        line_protocol.timestamp = timestamp;
+        Ok(line_protocol)
+    }
+
+    /// Create a tombstone data point marking a series as deleted at the given
+    /// timestamp
+    ///
+    /// The data point contains a single boolean field [TOMBSTONE_FIELD] set to
+    /// `true`. As the field key starts with an `_` (underscore) it has to be
+    /// built with [BuildOptions::allow_reserved_names] enabled
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = BuildOptions::new().allow_reserved_names(true);
+    /// let line = LineProtocol::tombstone("measurement", [("host", "a")], 1729270461612452700i64)
+    ///     .build_with(&options)
+    ///     .unwrap();
+    /// // Output: measurement,host=a _deleted=true 1729270461612452700
+    /// ```
+    ///
+    /// # Args
+    /// * `measurement` - The measurement of the deleted series
+    /// * `tags` - The tag key-value pairs of the deleted series
+    /// * `timestamp` - A unix timestamp
+    pub fn tombstone<M, I, K, V, T>(measurement: M, tags: I, timestamp: T) -> Self
+    where
+        M: Into<Measurement>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+        T: Into<i64>,
+    {
+        LineProtocol::tombstone_with_field(measurement, tags, timestamp, TOMBSTONE_FIELD)
+    }
+
+    /// Create a tombstone data point using a custom field key, see
+    /// [LineProtocol::tombstone]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::tombstone_with_field(
+    ///     "measurement",
+    ///     [("host", "a")],
+    ///     1729270461612452700i64,
+    ///     "deleted",
+    /// );
+    /// // Output: measurement,host=a deleted=true 1729270461612452700
+    /// ```
+    ///
+    /// # Args
+    /// * `measurement` - The measurement of the deleted series
+    /// * `tags` - The tag key-value pairs of the deleted series
+    /// * `timestamp` - A unix timestamp
+    /// * `field` - The field key marking the data point as a tombstone
+    pub fn tombstone_with_field<M, I, K, V, T, F>(
+        measurement: M,
+        tags: I,
+        timestamp: T,
+        field: F,
+    ) -> Self
+    where
+        M: Into<Measurement>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+        T: Into<i64>,
+        F: Into<FieldKey>,
+    {
+        LineProtocol::new(measurement)
+            .add_tags(tags)
+            .add_field(field, true)
+            .with_timestamp(timestamp)
     }
 
     /// Overwrite the measurement name with a new name
@@ -94,39 +688,136 @@ impl LineProtocol {
         V: Into<TagValue>,
     {
         self.tags
-            .get_or_insert(HashMap::new())
+            .get_or_insert(Map::new())
             .insert(key.into(), value.into());
         self
     }
 
-    /// Add or update a [tag key-value pair](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#tag-set) to the data point
-    ///
-    /// This function is useful if you want to build a data point dynamically
+    /// Add or update a tag key-value pair only if the value is `Some`, see
+    /// [LineProtocol::add_tag]
     ///
     /// # Example
     /// ```rust
-    /// let line_protocol = LineProtocol::new("measurement");
-    ///
-    /// for (key, value) in tags {
-    ///     line_protocol.add_tag_ref(key, value);
-    /// }
+    /// let region: Option<&str> = None;
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag_opt("host", Some("server1"))
+    ///     .add_tag_opt("region", region)
+    ///     .add_field("field", "value");
+    /// // Output: measurement,host=server1 field="value"
     /// ```
     ///
     /// # Args
     /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
     ///   tag key
-    /// * `value` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    /// * `value` - An optional [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
     ///   tag value
-    pub fn add_tag_ref<K, V>(&mut self, key: K, value: V)
+    pub fn add_tag_opt<K, V>(self, key: K, value: Option<V>) -> Self
     where
         K: Into<TagKey>,
         V: Into<TagValue>,
     {
-        self.tags
-            .get_or_insert(HashMap::new())
+        match value {
+            Some(value) => self.add_tag(key, value),
+            None => self,
+        }
+    }
+
+    /// Add or update a [tag key-value pair](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#tag-set) to the data point
+    ///
+    /// This function is useful if you want to build a data point dynamically
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    ///
+    /// for (key, value) in tags {
+    ///     line_protocol.add_tag_ref(key, value);
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key
+    /// * `value` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag value
+    pub fn add_tag_ref<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        self.tags
+            .get_or_insert(Map::new())
             .insert(key.into(), value.into());
     }
 
+    /// Add or update multiple [tag key-value pairs](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#tag-set) to the data point
+    ///
+    /// This function is useful if you want to follow a builder pattern
+    ///
+    /// # Example
+    /// ```rust
+    /// let tags = vec![("key1", "value"), ("key2", "value")];
+    /// let line_protocol = LineProtocol::new("measurement").add_tags(tags);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key-value pairs
+    pub fn add_tags<I, K, V>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        self.add_tags_ref(iter);
+        self
+    }
+
+    /// Add or update multiple [tag key-value pairs](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#tag-set) to the data point
+    ///
+    /// This function is useful if you want to build a data point dynamically
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.add_tags_ref(tags);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key-value pairs
+    pub fn add_tags_ref<I, K, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        for (key, value) in iter {
+            self.add_tag_ref(key, value);
+        }
+    }
+
+    /// Add or update tag key-value pairs from a collection, same as
+    /// [LineProtocol::add_tags_ref]
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.extend_tags(vec![("host", "server1"), ("region", "eu")]);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key-value pairs
+    pub fn extend_tags<I, K, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<TagKey>,
+        V: Into<TagValue>,
+    {
+        self.add_tags_ref(iter);
+    }
+
     /// Delete a tag from the data point
     ///
     /// # Args
@@ -135,7 +826,7 @@ impl LineProtocol {
     where
         K: Into<TagKey>,
     {
-        self.tags.get_or_insert(HashMap::new()).remove(&key.into());
+        map_remove(self.tags.get_or_insert(Map::new()), &key.into());
         self
     }
 
@@ -147,7 +838,43 @@ impl LineProtocol {
     where
         K: Into<TagKey>,
     {
-        self.tags.get_or_insert(HashMap::new()).remove(&key.into());
+        map_remove(self.tags.get_or_insert(Map::new()), &key.into());
+    }
+
+    /// Rename a tag while keeping its value
+    ///
+    /// Returns whether a tag was renamed, which is not the case if `from` does
+    /// not exist. If a tag with the new key already exists its value is
+    /// **overwritten** by the value of the renamed tag
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "server1")
+    ///     .add_field("field", "value");
+    ///
+    /// let renamed = line_protocol.rename_tag("host", "hostname");
+    /// // Output: measurement,hostname=server1 field="value"
+    /// ```
+    ///
+    /// # Args
+    /// * `from` - The current [TagKey]
+    /// * `to` - The new [TagKey]
+    pub fn rename_tag<K>(&mut self, from: K, to: K) -> bool
+    where
+        K: Into<TagKey>,
+    {
+        let Some(tags) = &mut self.tags else {
+            return false;
+        };
+
+        match map_remove(tags, &from.into()) {
+            Some(value) => {
+                tags.insert(to.into(), value);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Add or update a [field key-value pair](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#field-set) to the data point
@@ -173,6 +900,33 @@ impl LineProtocol {
         self
     }
 
+    /// Add or update a field key-value pair only if the value is `Some`, see
+    /// [LineProtocol::add_field]
+    ///
+    /// # Example
+    /// ```rust
+    /// let humidity: Option<f64> = None;
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field_opt("temperature", Some(21.5))
+    ///     .add_field_opt("humidity", humidity);
+    /// // Output: measurement temperature=21.5
+    /// ```
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key
+    /// * `value` - An optional field value
+    pub fn add_field_opt<K, V>(self, key: K, value: Option<V>) -> Self
+    where
+        K: Into<FieldKey>,
+        V: Into<FieldValue>,
+    {
+        match value {
+            Some(value) => self.add_field(key, value),
+            None => self,
+        }
+    }
+
     /// Add or update a [field key-value pair](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#field-set) to the data point
     ///
     /// This function is useful if you want to build a data point dynamically
@@ -199,6 +953,74 @@ impl LineProtocol {
         self.fields.insert(key.into(), value.into());
     }
 
+    /// Add or update multiple [field key-value pairs](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#field-set) to the data point
+    ///
+    /// This function is useful if you want to follow a builder pattern
+    ///
+    /// # Example
+    /// ```rust
+    /// let fields = vec![("key1", "value"), ("key2", "value")];
+    /// let line_protocol = LineProtocol::new("measurement").add_fields(fields);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key-value pairs
+    pub fn add_fields<I, K, V>(mut self, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<FieldKey>,
+        V: Into<FieldValue>,
+    {
+        self.add_fields_ref(iter);
+        self
+    }
+
+    /// Add or update multiple [field key-value pairs](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#field-set) to the data point
+    ///
+    /// This function is useful if you want to build a data point dynamically
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.add_fields_ref(fields);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key-value pairs
+    pub fn add_fields_ref<I, K, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<FieldKey>,
+        V: Into<FieldValue>,
+    {
+        for (key, value) in iter {
+            self.add_field_ref(key, value);
+        }
+    }
+
+    /// Add or update field key-value pairs from a collection, same as
+    /// [LineProtocol::add_fields_ref]
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.extend_fields(vec![("temperature", 21.5), ("humidity", 40.0)]);
+    /// ```
+    ///
+    /// # Args
+    /// * `iter` - An iterator of [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key-value pairs
+    pub fn extend_fields<I, K, V>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<FieldKey>,
+        V: Into<FieldValue>,
+    {
+        self.add_fields_ref(iter);
+    }
+
     /// Delete a field from the data point
     ///
     /// # Args
@@ -207,7 +1029,7 @@ impl LineProtocol {
     where
         K: Into<FieldKey>,
     {
-        self.fields.remove(&key.into());
+        map_remove(&mut self.fields, &key.into());
         self
     }
 
@@ -219,125 +1041,1055 @@ impl LineProtocol {
     where
         K: Into<FieldKey>,
     {
-        self.fields.remove(&key.into());
+        map_remove(&mut self.fields, &key.into());
     }
 
-    /// Set the timestamp for the data point
+    /// Keep only the tags for which the predicate returns `true`, see
+    /// [HashMap::retain](std::collections::HashMap::retain)
     ///
-    /// It is [recommend](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#timestamp)
-    /// to set a timestamp. By default InfluxDB v2 expects the timestamp to be
-    /// in nanosecond precision. If you are using any other form of
-    /// precision it needs to be explicitly set when making the query
+    /// The tag set is removed entirely if no tags are left
     ///
     /// # Example
     /// ```rust
-    /// let line_protocol = LineProtocol::new("measurement");
-    ///     .with_timestamp(1729270461612452700i64);
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "server1")
+    ///     .add_tag("internal_id", "42")
+    ///     .add_field("field", "value");
+    ///
+    /// line_protocol.retain_tags(|key, _| !key.0.starts_with("internal_"));
+    /// // Output: measurement,host=server1 field="value"
     /// ```
     ///
     /// # Args
-    /// * `timestamp` - A unix timestamp
-    pub fn with_timestamp<T>(mut self, timestamp: T) -> Self
+    /// * `f` - The predicate deciding which tags to keep
+    pub fn retain_tags<F>(&mut self, mut f: F)
     where
-        T: Into<i64>,
+        F: FnMut(&TagKey, &TagValue) -> bool,
     {
-        self.timestamp = Some(timestamp.into());
-        self
+        if let Some(tags) = &mut self.tags {
+            tags.retain(|key, value| f(key, value));
+            if tags.is_empty() {
+                self.tags = None;
+            }
+        }
     }
 
-    /// Set the timestamp for the data point
-    ///
-    /// It is [recommend](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#timestamp)
-    /// to set a timestamp. By default InfluxDB v2 expects the timestamp to be
-    /// in nanosecond precision. If you are using any other form of
-    /// precision it needs to be explicitly set when making the query
+    /// Keep only the fields for which the predicate returns `true`, see
+    /// [HashMap::retain](std::collections::HashMap::retain)
     ///
     /// # Example
     /// ```rust
-    /// let line_protocol = LineProtocol::new("measurement");
-    /// line_protocol.with_timestamp_ref(1729270461612452700i64);
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("value", 0.5)
+    ///     .add_field("debug", "trace");
+    ///
+    /// line_protocol.retain_fields(|_, value| !matches!(value, FieldValue::String(_)));
+    /// // Output: measurement value=0.5
     /// ```
     ///
     /// # Args
-    /// * `timestamp` - A unix timestamp
-    pub fn with_timestamp_ref<T>(&mut self, timestamp: T)
+    /// * `f` - The predicate deciding which fields to keep
+    pub fn retain_fields<F>(&mut self, mut f: F)
     where
-        T: Into<i64>,
+        F: FnMut(&FieldKey, &FieldValue) -> bool,
     {
-        self.timestamp = Some(timestamp.into());
+        self.fields.retain(|key, value| f(key, value));
     }
 
-    /// Delete the set timestamp
+    /// Rename a field while keeping its value
+    ///
+    /// Returns whether a field was renamed, which is not the case if `from`
+    /// does not exist. If a field with the new key already exists its value is
+    /// **overwritten** by the value of the renamed field
     ///
     /// # Example
     /// ```rust
-    /// let mut line_protocol = LineProtocol::new("measurement")
-    ///     .add_field("key", "value")
-    ///     .with_timestamp_ref(1729270461612452700i64);
+    /// let mut line_protocol = LineProtocol::new("measurement").add_field("temp", 21.5);
     ///
-    /// line_protocol = line_protocol.delete_timestamp();
+    /// let renamed = line_protocol.rename_field("temp", "temperature");
+    /// // Output: measurement temperature=21.5
     /// ```
-    pub fn delete_timestamp(mut self) -> Self {
-        self.timestamp = None;
-        self
+    ///
+    /// # Args
+    /// * `from` - The current [FieldKey]
+    /// * `to` - The new [FieldKey]
+    pub fn rename_field<K>(&mut self, from: K, to: K) -> bool
+    where
+        K: Into<FieldKey>,
+    {
+        match map_remove(&mut self.fields, &from.into()) {
+            Some(value) => {
+                self.fields.insert(to.into(), value);
+                true
+            }
+            None => false,
+        }
     }
 
-    /// Delete the set timestamp
+    /// Convert the type of an existing field while keeping its value
+    ///
+    /// See [FieldValue::convert_to] for which conversions are possible. The
+    /// field is left untouched if the conversion fails
     ///
     /// # Example
     /// ```rust
-    /// let mut line_protocol = LineProtocol::new("measurement")
-    ///     .add_field("key", "value")
-    ///     .with_timestamp_ref(1729270461612452700i64);
-    ///
-    /// line_protocol.delete_timestamp();
+    /// let mut line_protocol = LineProtocol::new("measurement").add_field("field", 10);
+    /// line_protocol
+    ///     .retype_field("field", FieldType::Float)
+    ///     .unwrap();
+    /// // Output: measurement field=10
     /// ```
-    pub fn delete_timestamp_ref(&mut self) {
-        self.timestamp = None;
+    ///
+    /// # Args
+    /// * `key` - An existing [FieldKey]
+    /// * `target` - The type to convert the field value to
+    pub fn retype_field<K>(&mut self, key: K, target: FieldType) -> Result<()>
+    where
+        K: Into<FieldKey>,
+    {
+        let key = key.into();
+        let Some(value) = self.fields.get_mut(&key) else {
+            return Err(BuilderError::UnknownField(key.to_string()).into());
+        };
+
+        *value = value
+            .convert_to(target)
+            .ok_or_else(|| BuilderError::FieldConversion {
+                key: key.to_string(),
+                from: value.field_type(),
+                to: target,
+            })?;
+        Ok(())
     }
 
-    /// Builds an InfluxDB v2 data point using the previously defined
-    /// measurement name, optional tags, fields, and an optional timestamp
+    /// Round every float field to a number of significant digits, e.g., to
+    /// remove jitter from high precision sensor readings
     ///
-    /// In addition validation checks are performed on the individual parts
-    pub fn build(&self) -> Result<String> {
-        if self.measurement.0.is_empty() {
-            return Err(BuilderError::EmptyMeasurement.into());
+    /// Fields of other types, zero and non-finite floats are left untouched.
+    /// A `sig_digits` of 0 leaves all fields untouched
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement").add_field("field", 3.14159265);
+    /// line_protocol.round_float_fields(4);
+    /// // Output: measurement field=3.142
+    /// ```
+    ///
+    /// # Args
+    /// * `sig_digits` - The number of significant digits to keep
+    pub fn round_float_fields(&mut self, sig_digits: u32) {
+        if sig_digits == 0 {
+            return;
         }
 
-        if self.measurement.0.starts_with("_") {
-            return Err(BuilderError::InvalidMeasurement.into());
+        for value in self.fields.values_mut() {
+            let FieldValue::Float(number) = value else {
+                continue;
+            };
+            if *number == 0.0 || !number.is_finite() {
+                continue;
+            }
+
+            // Round in decimal through the scientific notation and parse it back,
+            // scaling by a power of ten is inexact at both ends of the f64 range.
+            // More than 17 significant digits already represent any f64 exactly
+            let precision = sig_digits.min(17) as usize - 1;
+            let rounded = format!("{number:.precision$e}").parse::<f64>().ok();
+
+            // Rounding the largest numbers up can overflow, keep those as is
+            if let Some(rounded) = rounded.filter(|rounded| rounded.is_finite()) {
+                *number = rounded;
+            }
         }
+    }
 
-        let mut line_protocol = format!("{}", self.measurement.escape());
+    /// Merge the tags and fields of another data point into this one, with
+    /// the values of `other` winning on key conflicts
+    ///
+    /// The measurement is left untouched, as is the timestamp unless this data
+    /// point has none in which case the timestamp of `other` is adopted
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "a")
+    ///     .add_field("temperature", 21.5);
+    /// let other = LineProtocol::new("other")
+    ///     .add_field("temperature", 22.0)
+    ///     .add_field("humidity", 40)
+    ///     .with_timestamp(1729270461612452700i64);
+    ///
+    /// line_protocol.merge(other);
+    /// // Output: measurement,host=a humidity=40i,temperature=22 1729270461612452700
+    /// ```
+    ///
+    /// # Args
+    /// * `other` - The data point to merge tags and fields from
+    pub fn merge(&mut self, other: LineProtocol) {
+        self.merge_with_strategy(other, ConflictPolicy::KeepOther);
+    }
 
-        if let Some(tags) = &self.tags {
-            let mut formatted_tags = Vec::new();
-            for (key, value) in tags {
-                // Influx naming restriction
-                // https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/#naming-restrictions
-                if key.0.is_empty() {
-                    return Err(BuilderError::EmptyTagKey.into());
+    /// Merge the tags and fields of another data point into this one, using
+    /// the [ConflictPolicy] to decide which value is kept on key conflicts
+    ///
+    /// See [LineProtocol::merge]
+    ///
+    /// # Example
+    /// ```rust
+    /// line_protocol.merge_with_strategy(other, ConflictPolicy::KeepSelf);
+    /// ```
+    ///
+    /// # Args
+    /// * `other` - The data point to merge tags and fields from
+    /// * `policy` - Which value to keep when both data points share a key
+    pub fn merge_with_strategy(&mut self, other: LineProtocol, policy: ConflictPolicy) {
+        if let Some(other_tags) = other.tags.filter(|tags| !tags.is_empty()) {
+            let tags = self.tags.get_or_insert(Map::new());
+            for (key, value) in other_tags {
+                match policy {
+                    ConflictPolicy::KeepSelf => {
+                        tags.entry(key).or_insert(value);
+                    }
+                    ConflictPolicy::KeepOther => {
+                        tags.insert(key, value);
+                    }
                 }
+            }
+        }
 
-                if key.0.starts_with("_") {
-                    return Err(BuilderError::InvalidTagKey.into());
+        for (key, value) in other.fields {
+            match policy {
+                ConflictPolicy::KeepSelf => {
+                    self.fields.entry(key).or_insert(value);
                 }
-
-                if value.0.is_empty() {
-                    return Err(BuilderError::EmptyTagValue.into());
+                ConflictPolicy::KeepOther => {
+                    self.fields.insert(key, value);
+                }
+            }
+        }
+
+        if self.timestamp.is_none() && other.timestamp.is_some() {
+            self.timestamp = other.timestamp;
+            self.precision = other.precision;
+        }
+    }
+
+    /// Merge the fields of another data point into this one, preferring
+    /// the incoming values unless they are empty strings
+    ///
+    /// Useful when combining partial observations where an empty string means
+    /// the value was not observed. Empty strings are never merged, meaning an
+    /// existing field is kept as is
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement").add_field("status", "ok");
+    /// let update = LineProtocol::new("measurement")
+    ///     .add_field("status", "")
+    ///     .add_field("load", 0.5);
+    ///
+    /// line_protocol.merge_prefer_nonempty(update);
+    /// // Output: measurement load=0.5,status="ok"
+    /// ```
+    ///
+    /// # Args
+    /// * `other` - The data point to merge fields from
+    pub fn merge_prefer_nonempty(&mut self, other: LineProtocol) {
+        for (key, value) in other.fields {
+            if matches!(&value, FieldValue::String(string) if string.is_empty()) {
+                continue;
+            }
+
+            self.fields.insert(key, value);
+        }
+    }
+
+    /// Apply a transformation to the data point only if the condition is true
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .when(cfg!(debug_assertions), |lp| lp.add_tag("debug", "true"));
+    /// ```
+    ///
+    /// # Args
+    /// * `cond` - Whether to apply the transformation
+    /// * `f` - The transformation applied to the data point
+    pub fn when<F>(self, cond: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        match cond {
+            true => f(self),
+            false => self,
+        }
+    }
+
+    /// Set the timestamp for the data point
+    ///
+    /// It is [recommend](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#timestamp)
+    /// to set a timestamp. By default InfluxDB v2 expects the timestamp to be
+    /// in nanosecond precision. If you are using any other form of
+    /// precision it needs to be explicitly set when making the query
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    ///     .with_timestamp(1729270461612452700i64);
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp in nanoseconds
+    pub fn with_timestamp<T>(mut self, timestamp: T) -> Self
+    where
+        T: Into<i64>,
+    {
+        self.timestamp = Some(timestamp.into());
+        self.precision = TimestampPrecision::Nanoseconds;
+        self
+    }
+
+    /// Set the timestamp for the data point only if it is `Some`, see
+    /// [LineProtocol::with_timestamp]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .with_timestamp_opt(Some(1729270461612452700i64));
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - An optional unix timestamp in nanoseconds
+    pub fn with_timestamp_opt<T>(self, timestamp: Option<T>) -> Self
+    where
+        T: Into<i64>,
+    {
+        match timestamp {
+            Some(timestamp) => self.with_timestamp(timestamp),
+            None => self,
+        }
+    }
+
+    /// Set the timestamp for the data point
+    ///
+    /// It is [recommend](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#timestamp)
+    /// to set a timestamp. By default InfluxDB v2 expects the timestamp to be
+    /// in nanosecond precision. If you are using any other form of
+    /// precision it needs to be explicitly set when making the query
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.with_timestamp_ref(1729270461612452700i64);
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp in nanoseconds
+    pub fn with_timestamp_ref<T>(&mut self, timestamp: T)
+    where
+        T: Into<i64>,
+    {
+        self.timestamp = Some(timestamp.into());
+        self.precision = TimestampPrecision::Nanoseconds;
+    }
+
+    /// Set the timestamp for the data point to the current time in
+    /// nanoseconds
+    ///
+    /// A system clock set before the unix epoch results in a timestamp of 0
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .with_timestamp_now();
+    /// ```
+    pub fn with_timestamp_now(mut self) -> Self {
+        self.with_timestamp_now_ref();
+        self
+    }
+
+    /// Set the timestamp for the data point to the current time in
+    /// nanoseconds
+    ///
+    /// A system clock set before the unix epoch results in a timestamp of 0
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.with_timestamp_now_ref();
+    /// ```
+    pub fn with_timestamp_now_ref(&mut self) {
+        let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX),
+            Err(_) => 0,
+        };
+        self.with_timestamp_ref(timestamp);
+    }
+
+    /// Set the timestamp for the data point in the provided precision
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp
+    /// * `precision` - The precision of the timestamp
+    pub fn with_timestamp_precision<T>(
+        mut self,
+        timestamp: T,
+        precision: TimestampPrecision,
+    ) -> Self
+    where
+        T: Into<i64>,
+    {
+        self.timestamp = Some(timestamp.into());
+        self.precision = precision;
+        self
+    }
+
+    /// Set the timestamp for the data point in the provided precision
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement");
+    /// line_protocol.with_timestamp_precision_ref(1729270461i64, TimestampPrecision::Seconds);
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp
+    /// * `precision` - The precision of the timestamp
+    pub fn with_timestamp_precision_ref<T>(&mut self, timestamp: T, precision: TimestampPrecision)
+    where
+        T: Into<i64>,
+    {
+        self.timestamp = Some(timestamp.into());
+        self.precision = precision;
+    }
+
+    /// Delete the set timestamp
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("key", "value")
+    ///     .with_timestamp_ref(1729270461612452700i64);
+    ///
+    /// line_protocol = line_protocol.delete_timestamp();
+    /// ```
+    pub fn delete_timestamp(mut self) -> Self {
+        self.timestamp = None;
+        self
+    }
+
+    /// Delete the set timestamp
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("key", "value")
+    ///     .with_timestamp_ref(1729270461612452700i64);
+    ///
+    /// line_protocol.delete_timestamp();
+    /// ```
+    pub fn delete_timestamp_ref(&mut self) {
+        self.timestamp = None;
+    }
+
+    /// Remove all tags
+    ///
+    /// The tag set is removed entirely, meaning `tags` is `None` afterwards
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("tag", "value")
+    ///     .add_field("field", "value");
+    ///
+    /// line_protocol.clear_tags();
+    /// ```
+    pub fn clear_tags(&mut self) {
+        self.tags = None;
+    }
+
+    /// Remove all fields
+    ///
+    /// The field set keeps its allocated capacity, making it cheap to reuse
+    /// the data point as a scratch buffer
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+    ///
+    /// line_protocol.clear_fields();
+    /// ```
+    pub fn clear_fields(&mut self) {
+        self.fields.clear();
+    }
+
+    /// Reset everything except the measurement name, i.e., remove all tags,
+    /// fields, and the timestamp, and reset the timestamp precision to
+    /// nanoseconds
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut line_protocol = LineProtocol::new("measurement");
+    /// for sample in samples {
+    ///     line_protocol.clear();
+    ///     line_protocol.add_field_ref("value", sample.value);
+    ///     line_protocol.with_timestamp_ref(sample.timestamp);
+    ///     writer.write(&line_protocol.build().unwrap());
+    /// }
+    /// ```
+    pub fn clear(&mut self) {
+        self.clear_tags();
+        self.clear_fields();
+        self.delete_timestamp_ref();
+        self.precision = TimestampPrecision::default();
+    }
+
+    /// Remove all fields and the timestamp while keeping the measurement name
+    /// and tags
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "server01")
+    ///     .add_field("temperature", 21.5)
+    ///     .with_timestamp(1729270461612452700i64)
+    ///     .without_fields();
+    /// // line_protocol now only contains the measurement and the host tag
+    /// ```
+    pub fn without_fields(mut self) -> Self {
+        self.fields.clear();
+        self.timestamp = None;
+        self
+    }
+
+    /// Create a copy of the data point's measurement name and tags to use as
+    /// a template for points in the same series
+    ///
+    /// The template has no fields and no timestamp, see
+    /// [LineProtocol::without_fields]. Clone the template for each point
+    /// instead of inserting the same tags over and over
+    ///
+    /// # Example
+    /// ```rust
+    /// let template = LineProtocol::new("cpu")
+    ///     .add_tag("host", "server01")
+    ///     .add_tag("region", "eu-west")
+    ///     .template();
+    ///
+    /// let lines = samples
+    ///     .iter()
+    ///     .map(|(usage, timestamp)| {
+    ///         template
+    ///             .clone()
+    ///             .add_field("usage", *usage)
+    ///             .with_timestamp(*timestamp)
+    ///             .build()
+    ///     })
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// ```
+    pub fn template(&self) -> LineProtocol {
+        LineProtocol {
+            measurement: self.measurement.clone(),
+            tags: self.tags.clone(),
+            fields: Map::new(),
+            timestamp: None,
+            precision: self.precision,
+        }
+    }
+
+    /// Builds an InfluxDB v2 data point using the previously defined
+    /// measurement name, optional tags, fields, and an optional timestamp
+    ///
+    /// In addition validation checks are performed on the individual parts
+    pub fn build(&self) -> Result<String> {
+        self.build_with(&BuildOptions::default())
+    }
+
+    /// Builds an InfluxDB v2 data point using the provided timestamp instead of
+    /// the data point's own timestamp
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .with_timestamp(1729270461612452700i64)
+    ///     .build_with_timestamp(1729270461612452800)
+    ///     .unwrap();
+    /// // Output: measurement field="value" 1729270461612452800
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp
+    pub fn build_with_timestamp(&self, timestamp: i64) -> Result<String> {
+        self.build_with(&BuildOptions::new().timestamp(Some(timestamp)))
+    }
+
+    /// Builds an InfluxDB v2 data point with the timestamp converted to the
+    /// provided precision
+    ///
+    /// The timestamp is stored in the precision it was set with, nanoseconds
+    /// by default. Converting to a coarser precision truncates the timestamp
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .with_timestamp(1729270461612452700i64)
+    ///     .build_with_precision(TimestampPrecision::Seconds)
+    ///     .unwrap();
+    /// // Output: measurement field="value" 1729270461
+    /// ```
+    ///
+    /// # Args
+    /// * `precision` - The precision of the emitted timestamp
+    pub fn build_with_precision(&self, precision: TimestampPrecision) -> Result<String> {
+        let Some(timestamp) = self.timestamp else {
+            return self.build();
+        };
+
+        let converted = self
+            .precision
+            .convert(timestamp, precision)
+            .ok_or(BuilderError::TimestampOutOfRange(timestamp))?;
+        self.build_with_timestamp(converted)
+    }
+
+    /// Builds an InfluxDB v2 data point with the tags and fields in the order
+    /// they were added instead of sorted by key, e.g., for human readable
+    /// exports
+    ///
+    /// Requires the `indexmap` feature, without it the order is arbitrary.
+    /// The preserved order is only meant for readability, use
+    /// [LineProtocol::build] for data points written to InfluxDB. See
+    /// [BuildOptions::preserve_order]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = LineProtocol::new("measurement")
+    ///     .add_field("zeta", 1)
+    ///     .add_field("alpha", 2)
+    ///     .build_preserve_order()
+    ///     .unwrap();
+    /// // Output: measurement zeta=1i,alpha=2i
+    /// ```
+    pub fn build_preserve_order(&self) -> Result<String> {
+        self.build_with(&BuildOptions::new().preserve_order(true))
+    }
+
+    /// Builds an InfluxDB v2 data point with float field values written in
+    /// the provided [FloatFormat]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = LineProtocol::new("measurement")
+    ///     .add_field("field", 10.0)
+    ///     .build_with_float_format(FloatFormat::Fixed(3))
+    ///     .unwrap();
+    /// // Output: measurement field=10.000
+    /// ```
+    ///
+    /// # Args
+    /// * `format` - The format of float field values
+    pub fn build_with_float_format(&self, format: FloatFormat) -> Result<String> {
+        self.build_with(&BuildOptions::new().float_format(format))
+    }
+
+    /// Builds a normalized form of the data point, e.g., as a cache or
+    /// deduplication key
+    ///
+    /// Equivalent data points produce the same canonical line:
+    /// * tags and fields are sorted by their escaped key, e.g., `a` comes
+    ///   before `a-b`, and escaped the same way as [LineProtocol::build]
+    /// * an empty tag set is omitted
+    /// * `-0.0` is written as `0`
+    /// * the timestamp is converted to nanoseconds
+    ///
+    /// # Stability
+    /// The canonical line of a data point is stable across versions of this
+    /// crate. Any change to its output is considered a breaking change
+    ///
+    /// # Example
+    /// ```rust
+    /// let a = LineProtocol::new("measurement")
+    ///     .add_tag("b", "2")
+    ///     .add_tag("a", "1")
+    ///     .add_field("field", 0.0)
+    ///     .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+    /// let b = LineProtocol::new("measurement")
+    ///     .add_tag("a", "1")
+    ///     .add_tag("b", "2")
+    ///     .add_field("field", -0.0)
+    ///     .with_timestamp(1729270461000000000i64);
+    /// assert_eq!(a.canonical().unwrap(), b.canonical().unwrap());
+    /// // Output: measurement,a=1,b=2 field=0 1729270461000000000
+    /// ```
+    pub fn canonical(&self) -> Result<String> {
+        let mut canonical = self.clone();
+
+        if canonical.tags.as_ref().is_some_and(|tags| tags.is_empty()) {
+            canonical.tags = None;
+        }
+
+        for value in canonical.fields.values_mut() {
+            // Negative zero is equal to zero but formatted with a sign
+            if let FieldValue::Float(number) = value {
+                if *number == 0.0 {
+                    *number = 0.0;
+                }
+            }
+        }
+
+        if let Some(timestamp) = canonical.timestamp {
+            let converted = self
+                .precision
+                .convert(timestamp, TimestampPrecision::Nanoseconds)
+                .ok_or(BuilderError::TimestampOutOfRange(timestamp))?;
+            canonical.timestamp = Some(converted);
+            canonical.precision = TimestampPrecision::Nanoseconds;
+        }
+
+        canonical.build()
+    }
+
+    /// Builds an InfluxDB v2 data point which must stay within the provided
+    /// [BuildLimits]
+    ///
+    /// Exceeding a limit returns an error naming the offending limit, e.g.,
+    /// [BuilderError::TooManyTags]
+    ///
+    /// # Example
+    /// ```rust
+    /// let limits = BuildLimits::new().max_fields(Some(1));
+    /// let result = LineProtocol::new("measurement")
+    ///     .add_field("field1", 1)
+    ///     .add_field("field2", 2)
+    ///     .build_with_limits(&limits);
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// # Args
+    /// * `limits` - The limits enforced when building
+    pub fn build_with_limits(&self, limits: &BuildLimits) -> Result<String> {
+        self.build_with(&BuildOptions::new().limits(*limits))
+    }
+
+    /// Builds a batch of data points into a single string with one line per
+    /// data point
+    ///
+    /// The lines are joined with a newline, without a trailing newline. The
+    /// capacity of the string is estimated up front to avoid reallocating
+    /// while building large batches. The first data point which fails to
+    /// build returns [BuilderError::AtPoint] with its index in the batch
+    ///
+    /// # Example
+    /// ```rust
+    /// let points = LineProtocol::parse_lines(lines).unwrap();
+    /// let batch = LineProtocol::build_batch(&points).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `points` - A batch of data points
+    pub fn build_batch(points: &[LineProtocol]) -> Result<String> {
+        let capacity = points.iter().map(|point| point.estimated_len() + 1).sum();
+
+        let mut batch = String::with_capacity(capacity);
+        for (index, point) in points.iter().enumerate() {
+            let formatted = point
+                .format_with(&BuildOptions::default())
+                .map_err(|e| at_point(index, e))?;
+
+            if index > 0 {
+                batch.push('\n');
+            }
+            formatted.push_to(&mut batch);
+        }
+
+        Ok(batch)
+    }
+
+    /// Estimate the length in bytes of the built data point, ignoring escaping
+    fn estimated_len(&self) -> usize {
+        // The longest integer is 20 characters including the sign and suffix, most
+        // floats are shorter
+        const NUMBER_LEN: usize = 20;
+
+        let tags = self
+            .tags_iter()
+            .map(|(key, value)| key.0.len() + value.0.len() + 2)
+            .sum::<usize>();
+
+        let fields = self
+            .fields
+            .iter()
+            .map(|(key, value)| {
+                let value_len = match value {
+                    FieldValue::String(string) => string.len() + 2,
+                    _ => NUMBER_LEN,
+                };
+                key.0.len() + value_len + 2
+            })
+            .sum::<usize>();
+
+        let timestamp = self.timestamp.map_or(0, |_| NUMBER_LEN + 1);
+
+        self.measurement.0.len() + tags + fields + timestamp
+    }
+
+    /// Builds an InfluxDB v2 data point and returns it together with its
+    /// length in bytes, e.g., for tracking the size of a batch
+    ///
+    /// # Example
+    /// ```rust
+    /// let (line, len) = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .build_with_len()
+    ///     .unwrap();
+    /// ```
+    pub fn build_with_len(&self) -> Result<(String, usize)> {
+        let line = self.build()?;
+        let len = line.len();
+        Ok((line, len))
+    }
+
+    /// Builds an InfluxDB v2 data point as bytes and returns it together with
+    /// its length in bytes
+    ///
+    /// # Example
+    /// ```rust
+    /// let (bytes, len) = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .build_bytes_with_len()
+    ///     .unwrap();
+    /// ```
+    pub fn build_bytes_with_len(&self) -> Result<(Vec<u8>, usize)> {
+        let bytes = self.to_bytes()?;
+        let len = bytes.len();
+        Ok((bytes, len))
+    }
+
+    /// Builds an InfluxDB v2 data point using the provided [BuildOptions]
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = BuildOptions::new().max_total_tag_bytes(Some(1024));
+    /// let line = LineProtocol::new("measurement")
+    ///     .add_tag("tag", "value")
+    ///     .add_field("field", "value")
+    ///     .build_with(&options)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `options` - Options used when building the data point
+    pub fn build_with(&self, options: &BuildOptions) -> Result<String> {
+        let formatted = self.format_with(options)?;
+
+        let mut line_protocol = formatted.measurement.into_owned();
+        if let Some(tags) = formatted.tags {
+            line_protocol = format!("{line_protocol},{}", tags.join(","));
+        }
+
+        line_protocol = format!("{line_protocol} {}", formatted.fields.join(","));
+
+        if let Some(timestamp) = formatted.timestamp {
+            line_protocol = format!("{line_protocol} {timestamp}");
+        }
+
+        if let Some(max) = options.limits.max_line_bytes {
+            if line_protocol.len() > max {
+                let bytes = line_protocol.len();
+                return Err(BuilderError::LineTooLarge { bytes, max }.into());
+            }
+        }
+
+        Ok(line_protocol)
+    }
+
+    /// Writes an InfluxDB v2 data point followed by a newline directly to a
+    /// writer, e.g., a socket or file, without building the full line first
+    ///
+    /// The data point is validated the same way as [LineProtocol::build].
+    /// Nothing is written if validation fails, the [LineProtocolError] is
+    /// returned as an [io::Error] of kind [io::ErrorKind::InvalidData]
+    ///
+    /// [LineProtocolError]: crate::error::LineProtocolError
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut stream = TcpStream::connect("127.0.0.1:8089").unwrap();
+    /// LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .write_to(&mut stream)
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `w` - The writer the data point is written to
+    pub fn write_to<W>(&self, w: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        self.format_with(&BuildOptions::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .write(w)?;
+
+        w.write_all(b"\n")
+    }
+
+    /// Builds an InfluxDB v2 data point directly into a byte buffer, e.g., for
+    /// use as the body of an HTTP request
+    ///
+    /// Equal to [LineProtocol::build] converted to bytes without the
+    /// intermediate [String]
+    ///
+    /// # Example
+    /// ```rust
+    /// let bytes = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .to_bytes()
+    ///     .unwrap();
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Validates the data point and appends it to the byte buffer
+    pub(crate) fn write_bytes(&self, bytes: &mut Vec<u8>) -> Result<()> {
+        let formatted = self.format_with(&BuildOptions::default())?;
+
+        // Writing to a vector never fails
+        let _ = formatted.write(bytes);
+        Ok(())
+    }
+
+    /// Writes multiple data points to a writer, each followed by a newline
+    ///
+    /// See [LineProtocol::write_to]. Writing stops at the first data point
+    /// which fails validation, the data points before it are already written
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut file = File::create("points.lp").unwrap();
+    /// LineProtocol::write_all(&points, &mut file).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `points` - The data points to write
+    /// * `w` - The writer the data points are written to
+    pub fn write_all<W>(points: &[LineProtocol], w: &mut W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        for point in points {
+            point.write_to(w)?;
+        }
+        Ok(())
+    }
+
+    /// Validate the data point without building it
+    ///
+    /// Enforces the same invariants as [LineProtocol::build]:
+    /// * the measurement name is not empty, does not start with an `_`
+    ///   (underscore), and does not contain a newline or other control
+    ///   character
+    /// * tag keys and field keys are not empty and do not start with an `_`
+    ///   (underscore)
+    /// * tag values and string field values are not empty
+    /// * string field values do not contain a newline, carriage return, or any
+    ///   other control character except a tab
+    /// * float field values are finite
+    /// * atleast one field is present
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+    /// line_protocol.validate().unwrap();
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with(&BuildOptions::default())
+    }
+
+    /// Validates the data point using the provided [BuildOptions]
+    fn validate_with(&self, options: &BuildOptions) -> Result<()> {
+        if self.measurement.0.is_empty() {
+            return Err(BuilderError::EmptyMeasurement.into());
+        }
+
+        if self.measurement.0.starts_with("_") && !options.allow_reserved_names {
+            return Err(BuilderError::InvalidMeasurement("start with '_' (underscore)").into());
+        }
+
+        // A newline would split the data point into multiple lines
+        if self.measurement.0.contains('\n') {
+            return Err(BuilderError::InvalidMeasurement("contain a newline").into());
+        }
+
+        // Unlike string field values a measurement name is not quoted, other control
+        // characters, e.g., a carriage return, break the line as well
+        if self.measurement.0.chars().any(char::is_control) {
+            return Err(BuilderError::InvalidMeasurement("contain a control character").into());
+        }
+
+        let limits = &options.limits;
+        limits.check_key("measurement", &self.measurement.0)?;
+
+        if let Some(max) = limits.max_fields {
+            let count = self.fields.len();
+            if count > max {
+                return Err(BuilderError::TooManyFields { count, max }.into());
+            }
+        }
+
+        if let Some(tags) = &self.tags {
+            if let Some(max) = limits.max_tags {
+                let count = tags.len();
+                if count > max {
+                    return Err(BuilderError::TooManyTags { count, max }.into());
+                }
+            }
+
+            if let Some(max) = options.max_total_tag_bytes {
+                let bytes = tags
+                    .iter()
+                    .map(|(key, value)| key.0.len() + value.0.len())
+                    .sum();
+
+                if bytes > max {
+                    return Err(BuilderError::TagsTooLarge { bytes, max }.into());
+                }
+            }
+
+            for (key, value) in tags {
+                // Influx naming restriction
+                // https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/#naming-restrictions
+                if key.0.is_empty() {
+                    return Err(BuilderError::EmptyTagKey.into());
+                }
+
+                if key.0.starts_with("_") && !options.allow_reserved_names {
+                    return Err(BuilderError::InvalidTagKey.into());
+                }
+
+                limits.check_key("tag key", &key.0)?;
+
+                if value.0.is_empty() {
+                    return Err(BuilderError::EmptyTagValue.into());
                 }
-
-                formatted_tags.push(format!("{}={}", key.escape(), value.escape()));
             }
-
-            // Influx best practices
-            // https://docs.influxdata.com/influxdb/v2/write-data/best-practices/optimize-writes/#sort-tags-by-key
-            formatted_tags.sort();
-            line_protocol = format!("{line_protocol},{}", formatted_tags.join(","))
         }
 
-        let mut formatted_fields = Vec::new();
         for (key, value) in &self.fields {
             // Influx naming restriction
             // https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/#naming-restrictions
@@ -345,144 +2097,1366 @@ impl LineProtocol {
                 return Err(BuilderError::EmptyFieldKey.into());
             }
 
-            if key.0.starts_with("_") {
-                return Err(BuilderError::InvalidFieldKey.into());
-            }
+            if key.0.starts_with("_") && !options.allow_reserved_names {
+                return Err(BuilderError::InvalidFieldKey.into());
+            }
+
+            limits.check_key("field key", &key.0)?;
+
+            if let FieldValue::String(string) = value {
+                if string.is_empty() {
+                    return Err(BuilderError::EmptyFieldValue.into());
+                }
+
+                if options.reject_blank_strings && string.trim().is_empty() {
+                    return Err(BuilderError::EmptyFieldValue.into());
+                }
+
+                // Control characters, e.g., a null byte, break ingestion even when quoted.
+                // A tab is allowed as it is plain whitespace
+                if string.contains('\0') {
+                    return Err(BuilderError::InvalidFieldValue {
+                        key: key.to_string(),
+                        reason: "contains a null byte",
+                    }
+                    .into());
+                }
+
+                // Line protocol has no escape sequence for a newline or carriage return and
+                // line based readers, e.g., LineProtocol::parse_lines, split the line on them
+                if string.contains(['\n', '\r']) {
+                    return Err(BuilderError::InvalidFieldValue {
+                        key: key.to_string(),
+                        reason: "contains a newline or carriage return",
+                    }
+                    .into());
+                }
+
+                if string.chars().any(|char| char.is_control() && char != '\t') {
+                    return Err(BuilderError::InvalidFieldValue {
+                        key: key.to_string(),
+                        reason: "contains a control character",
+                    }
+                    .into());
+                }
+            }
+
+            if let FieldValue::Float(number) = value {
+                if !number.is_finite() {
+                    return Err(BuilderError::NonFiniteFloat {
+                        key: key.to_string(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        if self.fields.is_empty() {
+            return Err(BuilderError::MissingFields.into());
+        }
+
+        Ok(())
+    }
+
+    /// Validates and escapes the individual parts of the data point
+    fn format_with(&self, options: &BuildOptions) -> Result<FormattedLine<'_>> {
+        self.validate_with(options)?;
+
+        // Sorted by the escaped key alone, sorting the joined `key=value` strings would
+        // place `a-b` before `a` as `-` sorts before `=`
+        let join_sorted = |mut pairs: Vec<(Cow<'_, str>, String)>| {
+            if !options.preserve_order {
+                pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+            }
+
+            pairs
+                .into_iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+        };
+
+        // Influx best practices
+        // https://docs.influxdata.com/influxdb/v2/write-data/best-practices/optimize-writes/#sort-tags-by-key
+        let formatted_tags = self.tags.as_ref().map(|tags| {
+            let pairs = tags
+                .iter()
+                .map(|(key, value)| (key.escape_cow(), value.escape_cow().into_owned()))
+                .collect();
+            join_sorted(pairs)
+        });
+
+        let mut pairs = Vec::new();
+        for (key, value) in &self.fields {
+            let value = match (value, options.numeric_suffixes) {
+                (FieldValue::Float(number), _) => options.float_format.format(*number),
+                (FieldValue::Integer(number), false) => number.to_string(),
+                (FieldValue::UInteger(number), false) => number.to_string(),
+                (value, _) => value.escape().to_string(),
+            };
+
+            pairs.push((key.escape_cow(), value));
+        }
+        let formatted_fields = join_sorted(pairs);
+
+        Ok(FormattedLine {
+            measurement: self.measurement.escape_cow(),
+            tags: formatted_tags,
+            fields: formatted_fields,
+            timestamp: options.timestamp.or(self.timestamp),
+        })
+    }
+}
+
+impl Extend<(TagKey, TagValue)> for LineProtocol {
+    fn extend<I: IntoIterator<Item = (TagKey, TagValue)>>(&mut self, iter: I) {
+        self.extend_tags(iter);
+    }
+}
+
+impl Extend<(FieldKey, FieldValue)> for LineProtocol {
+    fn extend<I: IntoIterator<Item = (FieldKey, FieldValue)>>(&mut self, iter: I) {
+        self.extend_fields(iter);
+    }
+}
+
+/// Wrap an error with the index of the data point it occured on
+fn at_point(index: usize, e: LineProtocolError) -> LineProtocolError {
+    let source: BoxError = match e {
+        LineProtocolError::BuilderError(e) => e.into(),
+        e => e.into(),
+    };
+
+    BuilderError::AtPoint { index, source }.into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::LineProtocolError;
+
+    #[test]
+    fn test_builder_valid_missing_tags() {
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64)
+            .build();
+        assert!(result.is_ok());
+
+        let line = result.unwrap();
+        assert_eq!(line, "measurement field=\"value\" 1729270461612452700")
+    }
+
+    #[test]
+    fn test_builder_valid() {
+        let result = LineProtocol::new("measurement")
+            .add_tag("tag1", "value")
+            .add_tag("tag2", "value")
+            .add_field("field1", "value")
+            .add_field("field2", "{\"foo\": \"bar\"}")
+            .add_field("field3", "[\"hello\", \"world\"]")
+            .add_field("field4", true)
+            .add_field("field5", 10.0)
+            .add_field("field6", 10)
+            .add_field("field7", 0.5)
+            .with_timestamp(1729270461612452700i64)
+            .build();
+        assert!(result.is_ok());
+
+        let line = result.unwrap();
+        assert_eq!(
+            line,
+            "measurement,tag1=value,tag2=value field1=\"value\",field2=\"{\\\"foo\\\": \
+             \\\"bar\\\"}\",field3=\"[\\\"hello\\\", \
+             \\\"world\\\"]\",field4=true,field5=10,field6=10i,field7=0.5 1729270461612452700"
+        )
+    }
+
+    #[test]
+    fn test_builder_unsigned_integer_suffix() {
+        let result = LineProtocol::new("m").add_field("f", 10u64).build();
+        assert_eq!(result.unwrap(), "m f=10u");
+
+        let result = LineProtocol::new("m").add_field("f", 10i64).build();
+        assert_eq!(result.unwrap(), "m f=10i");
+    }
+
+    #[test]
+    fn test_builder_missing_field_is_err() {
+        let result = LineProtocol::new("measurement").build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_empty_measurement_is_err() {
+        let result = LineProtocol::new("").add_field("field", "value").build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_invalid_measurement_is_err() {
+        let result = LineProtocol::new("_measurement")
+            .add_field("field", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_newline_measurement_is_err() {
+        let result = LineProtocol::new("measure\nment")
+            .add_field("field", "value")
+            .build();
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(
+                BuilderError::InvalidMeasurement(_)
+            ))
+        ));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "A builder error occured: measurement name cannot contain a newline"
+        );
+    }
+
+    #[test]
+    fn test_builder_control_character_measurement_is_err() {
+        for measurement in ["measure\rment", "measure\0ment", "measure\tment"] {
+            let result = LineProtocol::new(measurement)
+                .add_field("field", "value")
+                .build();
+            assert!(matches!(
+                result,
+                Err(LineProtocolError::BuilderError(
+                    BuilderError::InvalidMeasurement("contain a control character")
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_builder_empty_tag_key_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_tag("", "value")
+            .add_field("field", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_invalid_tag_key_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_tag("_tag", "value")
+            .add_field("field", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_empty_tag_value_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_tag("key", "")
+            .add_field("field", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_empty_field_key_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_field("", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_invalid_field_key_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("_field", "value")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_empty_field_value_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_from_columns() {
+        let result = LineProtocol::from_columns(
+            "measurement",
+            vec![("tag", "value")],
+            &["field1", "field2"],
+            &[FieldValue::Float(0.5), FieldValue::Boolean(true)],
+            Some(1729270461612452700),
+        );
+        assert!(result.is_ok());
+
+        let line = result.unwrap().build().unwrap();
+        assert_eq!(
+            line,
+            "measurement,tag=value field1=0.5,field2=true 1729270461612452700"
+        )
+    }
+
+    #[test]
+    fn test_builder_from_columns_length_mismatch_is_err() {
+        let result = LineProtocol::from_columns(
+            "measurement",
+            Vec::<(&str, &str)>::new(),
+            &["field1", "field2"],
+            &[FieldValue::Float(0.5)],
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(
+                BuilderError::ColumnLengthMismatch { keys: 2, values: 1 }
+            ))
+        ))
+    }
+
+    #[test]
+    fn test_builder_new_from_display() {
+        enum Sensor {
+            Temperature,
+        }
+
+        impl Display for Sensor {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Sensor::Temperature => write!(f, "temperature"),
+                }
+            }
+        }
+
+        let result = LineProtocol::new_from(Sensor::Temperature)
+            .add_field("field", 0.5)
+            .build();
+        assert_eq!(result.unwrap(), "temperature field=0.5")
+    }
+
+    #[test]
+    fn test_builder_max_total_tag_bytes() {
+        // Tag keys and values are 12 bytes combined
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag1", "va")
+            .add_tag("tag2", "lu")
+            .add_field("field", "value");
+
+        let options = BuildOptions::new().max_total_tag_bytes(Some(12));
+        assert!(line_protocol.build_with(&options).is_ok());
+
+        let options = BuildOptions::new().max_total_tag_bytes(Some(11));
+        let result = line_protocol.build_with(&options);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(
+                BuilderError::TagsTooLarge { bytes: 12, max: 11 }
+            ))
+        ));
+
+        // Unlimited by default
+        assert!(line_protocol.build().is_ok());
+    }
+
+    #[test]
+    fn test_builder_merge_prefer_nonempty() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("status", "ok")
+            .add_field("load", 0.1);
+
+        let update = LineProtocol::new("measurement")
+            .add_field("status", "")
+            .add_field("load", 0.5)
+            .add_field("host", "server")
+            .add_field("missing", "");
+        line_protocol.merge_prefer_nonempty(update);
+
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement host=\"server\",load=0.5,status=\"ok\""
+        );
+    }
+
+    #[test]
+    fn test_builder_build_with_timestamp() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64);
+
+        let result = line_protocol.build_with_timestamp(1729270461612452800);
+        assert_eq!(
+            result.unwrap(),
+            "measurement field=\"value\" 1729270461612452800"
+        );
+        assert_eq!(line_protocol.timestamp, Some(1729270461612452700));
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+        let result = line_protocol.build_with_timestamp(1729270461612452800);
+        assert_eq!(
+            result.unwrap(),
+            "measurement field=\"value\" 1729270461612452800"
+        );
+    }
+
+    #[test]
+    fn test_builder_control_character_field_value_is_err() {
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "null\0byte")
+            .build();
+        assert!(matches!(
+            &result,
+            Err(LineProtocolError::BuilderError(BuilderError::InvalidFieldValue { key, .. }))
+                if key == "field"
+        ));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "A builder error occured: invalid value for field field: contains a null byte"
+        );
+
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "bell\u{7}")
+            .build();
+        assert!(result.is_err());
+
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "tab\tseparated")
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_builder_without_numeric_suffixes() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field1", 10)
+            .add_field("field2", 10u64)
+            .add_field("field3", 10.5);
+
+        let options = BuildOptions::new().numeric_suffixes(false);
+        let result = line_protocol.build_with(&options);
+        assert_eq!(
+            result.unwrap(),
+            "measurement field1=10,field2=10,field3=10.5"
+        );
+
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement field1=10i,field2=10u,field3=10.5"
+        );
+    }
+
+    #[test]
+    fn test_builder_build_with_len() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "välue")
+            .add_field("field", "välue")
+            .with_timestamp(1729270461612452700i64);
+
+        let (line, len) = line_protocol.build_with_len().unwrap();
+        assert_eq!(line, line_protocol.build().unwrap());
+        assert_eq!(len, line.len());
+
+        let (bytes, len) = line_protocol.build_bytes_with_len().unwrap();
+        assert_eq!(bytes, line.as_bytes());
+        assert_eq!(len, bytes.len());
+
+        let result = LineProtocol::new("measurement").build_with_len();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_non_finite_float_is_err() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let result = LineProtocol::new("measurement")
+                .add_field("field", value)
+                .build();
+
+            assert!(matches!(
+                result,
+                Err(LineProtocolError::BuilderError(BuilderError::NonFiniteFloat { key }))
+                    if key == "field"
+            ));
+        }
+
+        let result = LineProtocol::new("measurement")
+            .add_field("field", f64::MAX)
+            .build();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_builder_build_with_precision() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64);
+
+        let line = line_protocol
+            .build_with_precision(TimestampPrecision::Seconds)
+            .unwrap();
+        assert_eq!(line, "measurement field=\"value\" 1729270461");
+
+        let line = line_protocol
+            .build_with_precision(TimestampPrecision::Milliseconds)
+            .unwrap();
+        assert_eq!(line, "measurement field=\"value\" 1729270461612");
+
+        // The nanosecond timestamp is emitted as is by default
+        let line = line_protocol
+            .build_with_precision(TimestampPrecision::Nanoseconds)
+            .unwrap();
+        assert_eq!(line, line_protocol.build().unwrap());
+
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+        let line = line_protocol
+            .build_with_precision(TimestampPrecision::Nanoseconds)
+            .unwrap();
+        assert_eq!(line, "measurement field=\"value\" 1729270461000000000");
+
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_timestamp_precision(i64::MAX, TimestampPrecision::Seconds)
+            .build_with_precision(TimestampPrecision::Nanoseconds);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(
+                BuilderError::TimestampOutOfRange(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_builder_when() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .when(true, |lp| lp.add_tag("applied", "true"))
+            .when(false, |lp| lp.add_tag("skipped", "true"));
+
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,applied=true field=\"value\""
+        );
+    }
+
+    #[test]
+    fn test_builder_add_tags_and_fields() {
+        let tags = vec![("tag2", "value"), ("tag1", "value")];
+        let fields = vec![("field2".to_string(), 2.5), ("field1".to_string(), 1.5)];
+
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tags(tags.clone())
+            .add_fields(fields.clone());
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,tag1=value,tag2=value field1=1.5,field2=2.5"
+        );
+
+        let mut line_protocol_ref = LineProtocol::new("measurement");
+        line_protocol_ref.add_tags_ref(tags);
+        line_protocol_ref.add_fields_ref(fields);
+        assert_eq!(
+            line_protocol_ref.build().unwrap(),
+            line_protocol.build().unwrap()
+        );
+
+        // No tags are added for an empty iterator
+        let line_protocol = LineProtocol::new("measurement").add_tags(Vec::<(&str, &str)>::new());
+        assert!(line_protocol.tags.is_none());
+    }
+
+    #[test]
+    fn test_builder_retype_field() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("integer", 10)
+            .add_field("string", "10.5");
+
+        line_protocol
+            .retype_field("integer", FieldType::Float)
+            .unwrap();
+        assert_eq!(
+            line_protocol.get_field("integer"),
+            Some(FieldValue::Float(10.0))
+        );
+
+        line_protocol
+            .retype_field("string", FieldType::Float)
+            .unwrap();
+        assert_eq!(
+            line_protocol.get_field("string"),
+            Some(FieldValue::Float(10.5))
+        );
+
+        // A float which is not a whole number can't become an integer
+        let result = line_protocol.retype_field("string", FieldType::Integer);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_builder_retype_field_is_err() {
+        let mut line_protocol = LineProtocol::new("measurement").add_field("field", "hello");
 
-            if let FieldValue::String(string) = value {
-                if string.is_empty() {
-                    return Err(BuilderError::EmptyFieldValue.into());
+        let result = line_protocol.retype_field("field", FieldType::Integer);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(
+                BuilderError::FieldConversion {
+                    from: FieldType::String,
+                    to: FieldType::Integer,
+                    ..
                 }
-            }
+            ))
+        ));
+        assert_eq!(
+            line_protocol.get_field("field"),
+            Some(FieldValue::String("hello".to_string()))
+        );
 
-            formatted_fields.push(format!("{}={}", key.escape(), value.escape()));
-        }
+        let mut line_protocol = LineProtocol::new("measurement").add_field("field", u64::MAX);
+        let error = line_protocol
+            .retype_field("field", FieldType::Integer)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "A builder error occured: field field cannot be converted from uinteger to integer"
+        );
 
-        if formatted_fields.is_empty() {
-            return Err(BuilderError::MissingFields.into());
-        }
+        let result = line_protocol.retype_field("missing", FieldType::Integer);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::BuilderError(BuilderError::UnknownField(
+                _
+            )))
+        ));
+    }
 
-        formatted_fields.sort();
-        line_protocol = format!("{line_protocol} {}", formatted_fields.join(","));
+    #[test]
+    fn test_builder_round_float_fields_range() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("max", 1e308)
+            .add_field("min_positive", f64::MIN_POSITIVE)
+            .add_field("subnormal", 1.23456e-310)
+            .add_field("overflow", f64::MAX);
+        line_protocol.round_float_fields(4);
 
-        if let Some(timestamp) = self.timestamp {
-            line_protocol = format!("{line_protocol} {timestamp}");
-        }
+        assert_eq!(
+            line_protocol.get_field("max"),
+            Some(FieldValue::Float(1e308))
+        );
+        assert_eq!(
+            line_protocol.get_field("min_positive"),
+            Some(FieldValue::Float(2.225e-308))
+        );
+        assert_eq!(
+            line_protocol.get_field("subnormal"),
+            Some(FieldValue::Float(1.235e-310))
+        );
+        assert_eq!(
+            line_protocol.get_field("overflow"),
+            Some(FieldValue::Float(f64::MAX))
+        );
+    }
 
-        Ok(line_protocol)
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_builder_round_float_fields() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("pi", 3.14159265)
+            .add_field("negative", -0.00123456)
+            .add_field("large", 123456.0)
+            .add_field("zero", 0.0)
+            .add_field("integer", 314159265)
+            .add_field("string", "3.14159265");
+        line_protocol.round_float_fields(4);
+
+        assert_eq!(
+            line_protocol.get_field("pi"),
+            Some(FieldValue::Float(3.142))
+        );
+        assert_eq!(
+            line_protocol.get_field("negative"),
+            Some(FieldValue::Float(-0.001235))
+        );
+        assert_eq!(
+            line_protocol.get_field("large"),
+            Some(FieldValue::Float(123500.0))
+        );
+        assert_eq!(
+            line_protocol.get_field("zero"),
+            Some(FieldValue::Float(0.0))
+        );
+        assert_eq!(
+            line_protocol.get_field("integer"),
+            Some(FieldValue::Integer(314159265))
+        );
+        assert_eq!(
+            line_protocol.get_field("string"),
+            Some(FieldValue::String("3.14159265".into()))
+        );
+
+        line_protocol.round_float_fields(0);
+        assert_eq!(
+            line_protocol.get_field("pi"),
+            Some(FieldValue::Float(3.142))
+        );
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[test]
+    fn test_builder_write_to() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag2", "value 2")
+            .add_tag("tag1", "value1")
+            .add_field("field2", "value")
+            .add_field("field1", 10)
+            .with_timestamp(1729270461612452700i64);
+
+        let mut buffer = Vec::new();
+        line_protocol.write_to(&mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            format!("{}\n", line_protocol.build().unwrap())
+        );
+
+        // Nothing is written for an invalid data point
+        let mut buffer = Vec::new();
+        let error = LineProtocol::new("measurement")
+            .write_to(&mut buffer)
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(buffer.is_empty());
+    }
 
     #[test]
-    fn test_builder_valid_missing_tags() {
-        let result = LineProtocol::new("measurement")
-            .add_field("field", "value")
-            .with_timestamp(1729270461612452700i64)
-            .build();
-        assert!(result.is_ok());
+    fn test_builder_write_all() {
+        let points = vec![
+            LineProtocol::new("measurement").add_field("field", 1),
+            LineProtocol::new("measurement")
+                .add_field("field", 2)
+                .with_timestamp(1729270461612452700i64),
+        ];
 
-        let line = result.unwrap();
-        assert_eq!(line, "measurement field=\"value\" 1729270461612452700")
+        let mut buffer = Vec::new();
+        LineProtocol::write_all(&points, &mut buffer).unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "measurement field=1i\nmeasurement field=2i 1729270461612452700\n"
+        );
     }
 
     #[test]
-    fn test_builder_valid() {
-        let result = LineProtocol::new("measurement")
-            .add_tag("tag1", "value")
-            .add_tag("tag2", "value")
-            .add_field("field1", "value")
-            .add_field("field2", "{\"foo\": \"bar\"}")
-            .add_field("field3", "[\"hello\", \"world\"]")
-            .add_field("field4", true)
-            .add_field("field5", 10.0)
-            .add_field("field6", 10)
-            .add_field("field7", 0.5)
-            .with_timestamp(1729270461612452700i64)
-            .build();
-        assert!(result.is_ok());
+    fn test_builder_tombstone() {
+        let tombstone = LineProtocol::tombstone(
+            "measurement",
+            [("host", "a"), ("region", "eu")],
+            1729270461612452700i64,
+        );
+        assert_eq!(tombstone.field_count(), 1);
+        assert_eq!(
+            tombstone.get_field(TOMBSTONE_FIELD),
+            Some(FieldValue::Boolean(true))
+        );
 
-        let line = result.unwrap();
+        // The reserved field key is rejected unless explicitly allowed
+        assert!(tombstone.build().is_err());
+
+        let options = BuildOptions::new().allow_reserved_names(true);
         assert_eq!(
-            line,
-            "measurement,tag1=value,tag2=value field1=\"value\",field2=\"{\\\"foo\\\": \
-             \\\"bar\\\"}\",field3=\"[\\\"hello\\\", \
-             \\\"world\\\"]\",field4=true,field5=10,field6=10i,field7=0.5 1729270461612452700"
-        )
+            tombstone.build_with(&options).unwrap(),
+            "measurement,host=a,region=eu _deleted=true 1729270461612452700"
+        );
+
+        let tombstone = LineProtocol::tombstone_with_field(
+            "measurement",
+            [("host", "a")],
+            1729270461612452700i64,
+            "deleted",
+        );
+        assert_eq!(
+            tombstone.build().unwrap(),
+            "measurement,host=a deleted=true 1729270461612452700"
+        );
     }
 
     #[test]
-    fn test_builder_missing_field_is_err() {
-        let result = LineProtocol::new("measurement").build();
-        assert!(result.is_err());
+    fn test_builder_allow_reserved_names() {
+        let line_protocol = LineProtocol::new("_measurement")
+            .add_tag("_tag", "value")
+            .add_field("_field", "value");
+        assert!(line_protocol.build().is_err());
+
+        let options = BuildOptions::new().allow_reserved_names(true);
+        assert_eq!(
+            line_protocol.build_with(&options).unwrap(),
+            "_measurement,_tag=value _field=\"value\""
+        );
     }
 
     #[test]
-    fn test_builder_empty_measurement_is_err() {
-        let result = LineProtocol::new("").add_field("field", "value").build();
-        assert!(result.is_err());
+    fn test_builder_to_bytes() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field1", "välue")
+            .add_field("field2", 0.5)
+            .with_timestamp(1729270461612452700i64);
+
+        assert_eq!(
+            line_protocol.to_bytes().unwrap(),
+            line_protocol.build().unwrap().into_bytes()
+        );
+
+        assert!(LineProtocol::new("measurement").to_bytes().is_err());
     }
 
     #[test]
-    fn test_builder_invalid_measurement_is_err() {
-        let result = LineProtocol::new("_measurement")
-            .add_field("field", "value")
-            .build();
-        assert!(result.is_err());
+    fn test_builder_rename_tag() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "server1")
+            .add_tag("region", "eu")
+            .add_field("field", "value");
+
+        assert!(line_protocol.rename_tag("host", "hostname"));
+        assert!(!line_protocol.rename_tag("host", "hostname"));
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,hostname=server1,region=eu field=\"value\""
+        );
+
+        // An existing tag is overwritten
+        assert!(line_protocol.rename_tag("hostname", "region"));
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,region=server1 field=\"value\""
+        );
+
+        let mut line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+        assert!(!line_protocol.rename_tag("host", "hostname"));
+        assert!(line_protocol.tags.is_none());
     }
 
     #[test]
-    fn test_builder_empty_tag_key_is_err() {
-        let result = LineProtocol::new("measurement")
-            .add_tag("", "value")
-            .add_field("field", "value")
-            .build();
-        assert!(result.is_err());
+    fn test_builder_rename_field() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("temp", 21.5)
+            .add_field("status", "ok");
+
+        assert!(line_protocol.rename_field("temp", "temperature"));
+        assert!(!line_protocol.rename_field("temp", "temperature"));
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement status=\"ok\",temperature=21.5"
+        );
+
+        // An existing field is overwritten
+        assert!(line_protocol.rename_field("temperature", "status"));
+        assert_eq!(line_protocol.build().unwrap(), "measurement status=21.5");
     }
 
     #[test]
-    fn test_builder_invalid_tag_key_is_err() {
-        let result = LineProtocol::new("measurement")
-            .add_tag("_tag", "value")
+    fn test_builder_retain_tags() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "server1")
+            .add_tag("internal_id", "42")
+            .add_tag("internal_rev", "7")
+            .add_field("field", "value");
+
+        line_protocol.retain_tags(|key, _| !key.0.starts_with("internal_"));
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=server1 field=\"value\""
+        );
+
+        // Removing all tags removes the tag set
+        line_protocol.retain_tags(|_, _| false);
+        assert!(line_protocol.tags.is_none());
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement field=\"value\""
+        );
+    }
+
+    #[test]
+    fn test_builder_retain_fields() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("value", 0.5)
+            .add_field("count", 3)
+            .add_field("debug", "trace");
+
+        line_protocol.retain_fields(|_, value| !matches!(value, FieldValue::String(_)));
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement count=3i,value=0.5"
+        );
+    }
+
+    #[test]
+    fn test_builder_merge() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "a")
+            .add_field("temperature", 21.5);
+        let other = LineProtocol::new("other")
+            .add_tag("host", "b")
+            .add_tag("region", "eu")
+            .add_field("temperature", 22.0)
+            .add_field("humidity", 40)
+            .with_timestamp(1729270461612452700i64);
+
+        line_protocol.merge(other.clone());
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=b,region=eu humidity=40i,temperature=22 1729270461612452700"
+        );
+
+        // The existing timestamp is kept
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_field("temperature", 21.5)
+            .with_timestamp(1i64);
+        line_protocol.merge(other);
+        assert_eq!(line_protocol.timestamp, Some(1));
+    }
+
+    #[test]
+    fn test_builder_merge_with_strategy() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "a")
+            .add_field("temperature", 21.5);
+        let other = LineProtocol::new("other")
+            .add_tag("host", "b")
+            .add_tag("region", "eu")
+            .add_field("temperature", 22.0)
+            .add_field("humidity", 40);
+
+        line_protocol.merge_with_strategy(other, ConflictPolicy::KeepSelf);
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=a,region=eu humidity=40i,temperature=21.5"
+        );
+
+        // Merging a data point without tags does not add an empty tag set
+        let mut line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        line_protocol.merge_with_strategy(
+            LineProtocol::new("measurement").add_field("other", 2),
+            ConflictPolicy::KeepSelf,
+        );
+        assert!(line_protocol.tags.is_none());
+    }
+
+    #[test]
+    fn test_builder_opt_some() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag_opt("tag", Some("value"))
+            .add_field_opt("field", Some(0.5))
+            .with_timestamp_opt(Some(1729270461612452700i64));
+
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,tag=value field=0.5 1729270461612452700"
+        );
+    }
+
+    #[test]
+    fn test_builder_opt_none() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 0.5);
+        let unchanged = line_protocol
+            .clone()
+            .add_tag_opt("tag", None::<&str>)
+            .add_field_opt("other", None::<i64>)
+            .with_timestamp_opt(None::<i64>);
+
+        assert_eq!(unchanged, line_protocol);
+        assert!(unchanged.tags.is_none());
+        assert!(unchanged.timestamp.is_none());
+    }
+
+    #[test]
+    fn test_builder_typestate() {
+        let builder = LineProtocol::builder("measurement")
+            .add_tag("tag", "value")
+            .with_timestamp(1729270461612452700i64)
+            .add_field("field1", "value")
+            .add_field("field2", 1);
+
+        assert_eq!(
+            builder.build().unwrap(),
+            "measurement,tag=value field1=\"value\",field2=1i 1729270461612452700"
+        );
+        assert_eq!(builder.into_inner().field_count(), 2);
+
+        // The measurement is still validated when building
+        assert!(LineProtocol::builder("")
             .add_field("field", "value")
-            .build();
-        assert!(result.is_err());
+            .build()
+            .is_err());
     }
 
     #[test]
-    fn test_builder_empty_tag_value_is_err() {
-        let result = LineProtocol::new("measurement")
-            .add_tag("key", "")
+    #[cfg(feature = "indexmap")]
+    fn test_builder_preserve_order() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("zone", "b")
+            .add_tag("host", "a")
+            .add_field("zeta", 1)
+            .add_field("mid", 2)
+            .add_field("alpha", 3);
+
+        assert_eq!(
+            line_protocol.build_preserve_order().unwrap(),
+            "measurement,zone=b,host=a zeta=1i,mid=2i,alpha=3i"
+        );
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=a,zone=b alpha=3i,mid=2i,zeta=1i"
+        );
+
+        // Deleting keeps the order of the remaining fields
+        line_protocol.delete_field_ref("mid");
+        assert_eq!(
+            line_protocol.build_preserve_order().unwrap(),
+            "measurement,zone=b,host=a zeta=1i,alpha=3i"
+        );
+
+        // Parsed lines keep the order of the input
+        let parsed = LineProtocol::parse_line("measurement b=1i,a=2i").unwrap();
+        assert_eq!(
+            parsed.build_preserve_order().unwrap(),
+            "measurement b=1i,a=2i"
+        );
+    }
+
+    #[test]
+    fn test_builder_with_timestamp_now() {
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos() as i64;
+
+        let line_protocol = LineProtocol::new("measurement")
             .add_field("field", "value")
-            .build();
-        assert!(result.is_err());
+            .with_timestamp_precision(1i64, TimestampPrecision::Seconds)
+            .with_timestamp_now();
+        let timestamp = line_protocol.timestamp.unwrap();
+
+        assert!(timestamp >= before);
+        assert_eq!(line_protocol.precision, TimestampPrecision::Nanoseconds);
+
+        let mut line_protocol = LineProtocol::new("measurement");
+        line_protocol.with_timestamp_now_ref();
+        assert!(line_protocol.timestamp.unwrap() >= timestamp);
     }
 
     #[test]
-    fn test_builder_empty_field_key_is_err() {
-        let result = LineProtocol::new("measurement")
-            .add_field("", "value")
-            .build();
-        assert!(result.is_err());
+    fn test_builder_extend() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "server1")
+            .add_field("temperature", 20.0);
+
+        line_protocol.extend_tags(vec![("host", "server2"), ("region", "eu")]);
+        line_protocol.extend_fields(vec![("temperature", 21.5), ("humidity", 40.0)]);
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=server2,region=eu humidity=40,temperature=21.5"
+        );
+
+        line_protocol.extend(vec![(TagKey::from("zone"), TagValue::from("a"))]);
+        line_protocol.extend(vec![(FieldKey::from("status"), FieldValue::from("ok"))]);
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement,host=server2,region=eu,zone=a humidity=40,status=\"ok\",temperature=21.5"
+        );
+
+        // Extending with nothing does not add an empty tag set
+        let mut line_protocol = LineProtocol::new("measurement");
+        line_protocol.extend(Vec::<(TagKey, TagValue)>::new());
+        assert!(line_protocol.tags.is_none());
     }
 
     #[test]
-    fn test_builder_invalid_field_key_is_err() {
-        let result = LineProtocol::new("measurement")
+    fn test_builder_validate() {
+        let line_protocol = LineProtocol::new("measurement")
             .add_tag("tag", "value")
-            .add_field("_field", "value")
-            .build();
-        assert!(result.is_err());
+            .add_field("field", "value");
+        assert!(line_protocol.validate().is_ok());
+
+        let invalid = [
+            LineProtocol::new("").add_field("field", "value"),
+            LineProtocol::new("_measurement").add_field("field", "value"),
+            LineProtocol::new("measurement")
+                .add_tag("tag", "")
+                .add_field("field", "value"),
+            LineProtocol::new("measurement").add_field("field", ""),
+            LineProtocol::new("measurement").add_field("field", f64::NAN),
+            LineProtocol::new("measurement"),
+        ];
+
+        for line_protocol in invalid {
+            assert!(line_protocol.validate().is_err());
+            assert!(line_protocol.build().is_err());
+        }
     }
 
     #[test]
-    fn test_builder_empty_field_value_is_err() {
-        let result = LineProtocol::new("measurement")
-            .add_field("field", "")
-            .build();
-        assert!(result.is_err());
+    fn test_builder_multi_line_string_field() {
+        for value in [
+            "{\n  \"key\": \"value\"\n}",
+            "first\r\nsecond",
+            "carriage\rreturn",
+        ] {
+            let result = LineProtocol::new("measurement")
+                .add_field("json", value)
+                .build();
+            assert!(matches!(
+                result,
+                Err(LineProtocolError::BuilderError(
+                    BuilderError::InvalidFieldValue {
+                        reason: "contains a newline or carriage return",
+                        ..
+                    }
+                ))
+            ));
+        }
+
+        // A literal backslash followed by an n is not a newline
+        let value = r"C:\new";
+        let line = LineProtocol::new("measurement")
+            .add_field("path", value)
+            .build()
+            .unwrap();
+        let parsed = LineProtocol::parse_line(&line).unwrap();
+        assert_eq!(parsed.get_field("path"), Some(FieldValue::from(value)));
+    }
+
+    #[test]
+    fn test_builder_canonical() {
+        let a = LineProtocol::new("measurement")
+            .add_tag("b", "2")
+            .add_tag("a", "1")
+            .add_field("float", 0.0)
+            .add_field("string", "value")
+            .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+        let b = LineProtocol::new("measurement")
+            .add_field("string", "value")
+            .add_field("float", -0.0)
+            .add_tag("a", "1")
+            .add_tag("b", "2")
+            .with_timestamp(1729270461000000000i64);
+
+        let expected = "measurement,a=1,b=2 float=0,string=\"value\" 1729270461000000000";
+        assert_eq!(a.canonical().unwrap(), expected);
+        assert_eq!(b.canonical().unwrap(), expected);
+
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .delete_tag("tag")
+            .add_field("field", 1);
+        assert_eq!(line_protocol.canonical().unwrap(), "measurement field=1i");
+    }
+
+    #[test]
+    fn test_builder_limits() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag1", "value")
+            .add_tag("tag2", "value")
+            .add_field("field1", 1)
+            .add_field("long_field_key", 2);
+
+        // No limits by default
+        assert!(line_protocol.build_with_limits(&BuildLimits::new()).is_ok());
+
+        let limits = BuildLimits::new().max_tags(Some(1));
+        assert!(matches!(
+            line_protocol.build_with_limits(&limits),
+            Err(LineProtocolError::BuilderError(BuilderError::TooManyTags {
+                count: 2,
+                max: 1
+            }))
+        ));
+
+        let limits = BuildLimits::new().max_fields(Some(1));
+        assert!(matches!(
+            line_protocol.build_with_limits(&limits),
+            Err(LineProtocolError::BuilderError(
+                BuilderError::TooManyFields { count: 2, max: 1 }
+            ))
+        ));
+
+        let limits = BuildLimits::new().max_key_len(Some(11));
+        match line_protocol.build_with_limits(&limits) {
+            Err(LineProtocolError::BuilderError(BuilderError::KeyTooLong { key, len, max })) => {
+                assert_eq!(key, "field key long_field_key");
+                assert_eq!((len, max), (14, 11));
+            }
+            other => panic!("expected key too long, got {other:?}"),
+        }
+
+        let line = line_protocol.build().unwrap();
+        let limits = BuildLimits::new().max_line_bytes(Some(line.len()));
+        assert_eq!(line_protocol.build_with_limits(&limits).unwrap(), line);
+
+        let limits = BuildLimits::new().max_line_bytes(Some(line.len() - 1));
+        assert!(matches!(
+            line_protocol.build_with_limits(&limits),
+            Err(LineProtocolError::BuilderError(
+                BuilderError::LineTooLarge { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_builder_template() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1)
+            .with_timestamp(1729270461612452700i64);
+
+        let template = line_protocol.template();
+        assert_eq!(template.get_tag("tag"), Some(TagValue::from("value")));
+        assert!(template.fields.is_empty());
+        assert_eq!(template.timestamp, None);
+
+        let line = template
+            .clone()
+            .add_field("other", 2)
+            .with_timestamp(1729270461612452800i64)
+            .build()
+            .unwrap();
+        assert_eq!(line, "measurement,tag=value other=2i 1729270461612452800");
+
+        let without_fields = line_protocol.without_fields();
+        assert_eq!(without_fields, template);
+    }
+
+    #[test]
+    fn test_builder_clear() {
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1)
+            .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+
+        line_protocol.clear_tags();
+        assert_eq!(line_protocol.tags, None);
+        assert!(line_protocol.contains_field("field"));
+
+        line_protocol.clear_fields();
+        assert!(line_protocol.fields.is_empty());
+        assert_eq!(line_protocol.timestamp, Some(1729270461));
+
+        let mut line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1)
+            .with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+
+        line_protocol.clear();
+        assert_eq!(line_protocol, LineProtocol::new("measurement"));
+    }
+
+    #[test]
+    fn test_builder_build_batch() {
+        let points = vec![
+            LineProtocol::new("measurement")
+                .add_tag("tag", "value")
+                .add_field("field", "hello")
+                .with_timestamp(1729270461612452700i64),
+            LineProtocol::new("measurement").add_field("field", 1.5),
+        ];
+
+        let batch = LineProtocol::build_batch(&points).unwrap();
+        let expected = points
+            .iter()
+            .map(|point| point.build().unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(batch, expected);
+        assert!(batch.capacity() >= batch.len());
+
+        assert_eq!(LineProtocol::build_batch(&[]).unwrap(), "");
+
+        let mut points = points;
+        points.push(LineProtocol::new("measurement"));
+        match LineProtocol::build_batch(&points) {
+            Err(LineProtocolError::BuilderError(BuilderError::AtPoint { index, source })) => {
+                assert_eq!(index, 2);
+                assert!(matches!(
+                    source.downcast_ref::<BuilderError>(),
+                    Some(BuilderError::MissingFields)
+                ));
+            }
+            other => panic!("expected error at point, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_builder_reject_blank_strings() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", " \t ");
+
+        // Accepted by default
+        assert_eq!(line_protocol.build().unwrap(), "measurement field=\" \t \"");
+
+        let options = BuildOptions::new().reject_blank_strings(true);
+        assert!(matches!(
+            line_protocol.build_with(&options),
+            Err(LineProtocolError::BuilderError(
+                BuilderError::EmptyFieldValue
+            ))
+        ));
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", " value ");
+        assert!(line_protocol.build_with(&options).is_ok());
+    }
+
+    #[test]
+    fn test_builder_float_format() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("a", 10.0)
+            .add_field("b", 0.1 + 0.2)
+            .add_field("c", 1500.0);
+
+        let formats = [
+            (
+                FloatFormat::Shortest,
+                "measurement a=10,b=0.30000000000000004,c=1500",
+            ),
+            (
+                FloatFormat::Fixed(3),
+                "measurement a=10.000,b=0.300,c=1500.000",
+            ),
+            (
+                FloatFormat::Scientific,
+                "measurement a=1e1,b=3.0000000000000004e-1,c=1.5e3",
+            ),
+        ];
+
+        for (format, expected) in formats {
+            let line = line_protocol.build_with_float_format(format).unwrap();
+            assert_eq!(line, expected);
+
+            // Every format parses back to a float
+            let parsed = LineProtocol::parse_line(&line).unwrap();
+            assert!(parsed
+                .fields_iter()
+                .all(|(_, value)| value.as_f64().is_some()));
+        }
+
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            line_protocol
+                .build_with_float_format(FloatFormat::Shortest)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_canonical_prefix_keys() {
+        let line_protocol = LineProtocol::new("m")
+            .add_tag("a-b", "2")
+            .add_tag("a", "1")
+            .add_field("x-y", 2)
+            .add_field("x", 1);
+
+        let expected = "m,a=1,a-b=2 x=1i,x-y=2i";
+        assert_eq!(line_protocol.canonical().unwrap(), expected);
+        assert_eq!(line_protocol.build().unwrap(), expected);
     }
 }
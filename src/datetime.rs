@@ -0,0 +1,97 @@
+//! Conversion between a data point's timestamp and a chrono
+//! [DateTime](chrono::DateTime)
+//!
+//! Requires the `chrono` feature
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    error::{BuilderError, Result},
+    precision::TimestampPrecision,
+    LineProtocol,
+};
+
+impl LineProtocol {
+    /// Set the timestamp for the data point from a [DateTime]
+    ///
+    /// The timestamp is stored in nanoseconds. An error is returned if the date
+    /// is outside the range of a nanosecond timestamp, i.e., roughly before
+    /// 1677 or after 2262
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .with_datetime(Utc::now())
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `dt` - The time the data point was observed
+    pub fn with_datetime(self, dt: DateTime<Utc>) -> Result<Self> {
+        let timestamp = dt
+            .timestamp_nanos_opt()
+            .ok_or_else(|| BuilderError::DateTimeOutOfRange(dt.to_rfc3339()))?;
+        Ok(self.with_timestamp(timestamp))
+    }
+
+    /// Get the timestamp of the data point as a [DateTime]
+    ///
+    /// The timestamp is interpreted in its precision, see
+    /// [LineProtocol::precision]. `None` is returned if the data point has no
+    /// timestamp or it cannot be represented as a [DateTime]
+    ///
+    /// # Example
+    /// ```rust
+    /// let dt = line_protocol.timestamp_datetime();
+    /// ```
+    pub fn timestamp_datetime(&self) -> Option<DateTime<Utc>> {
+        let timestamp = self
+            .precision
+            .convert(self.timestamp?, TimestampPrecision::Nanoseconds)?;
+        Some(DateTime::from_timestamp_nanos(timestamp))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_datetime_round_trip() {
+        let dt = DateTime::from_timestamp_nanos(1729270461612452700);
+        let line_protocol = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_datetime(dt)
+            .unwrap();
+
+        assert_eq!(line_protocol.timestamp, Some(1729270461612452700));
+        assert_eq!(line_protocol.timestamp_datetime(), Some(dt));
+
+        // Timestamps in another precision are converted
+        let line_protocol =
+            line_protocol.with_timestamp_precision(1729270461i64, TimestampPrecision::Seconds);
+        assert_eq!(
+            line_protocol.timestamp_datetime(),
+            DateTime::from_timestamp(1729270461, 0)
+        );
+
+        let line_protocol = line_protocol.delete_timestamp();
+        assert_eq!(line_protocol.timestamp_datetime(), None);
+    }
+
+    #[test]
+    fn test_datetime_out_of_range() {
+        let dt = DateTime::from_timestamp(32503680000, 0).unwrap(); // Year 3000
+        let result = LineProtocol::new("measurement")
+            .add_field("field", "value")
+            .with_datetime(dt);
+
+        assert!(matches!(
+            result,
+            Err(crate::error::LineProtocolError::BuilderError(
+                BuilderError::DateTimeOutOfRange(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,143 @@
+//! Deserialization of a data point's field set into any type implementing
+//! serde's [Deserialize](serde::Deserialize) trait
+//!
+//! Requires the `serde` feature
+
+use std::marker::PhantomData;
+
+use serde::de::{self, value::MapDeserializer, DeserializeOwned, IntoDeserializer, Visitor};
+
+use crate::{element::FieldValue, error::Result, LineProtocol};
+
+/// A deserializer holding a single [FieldValue]
+pub struct FieldValueDeserializer<E> {
+    value: FieldValue,
+    marker: PhantomData<E>,
+}
+
+impl<'de, E> IntoDeserializer<'de, E> for FieldValue
+where
+    E: de::Error,
+{
+    type Deserializer = FieldValueDeserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        FieldValueDeserializer {
+            value: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> de::Deserializer<'de> for FieldValueDeserializer<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> std::result::Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            FieldValue::Float(number) => visitor.visit_f64(number),
+            FieldValue::Integer(number) => visitor.visit_i64(number),
+            FieldValue::UInteger(number) => visitor.visit_u64(number),
+            FieldValue::String(string) => visitor.visit_string(string),
+            FieldValue::Boolean(boolean) => visitor.visit_bool(boolean),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> std::result::Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // A field is only present if it has a value
+        visitor.visit_some(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl LineProtocol {
+    /// Deserialize the field set of the data point into a type implementing
+    /// [Deserialize](serde::Deserialize)
+    ///
+    /// Each field key is mapped to the struct member of the same name. Fields
+    /// without a matching member are ignored
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(Deserialize)]
+    /// struct Cpu {
+    ///     usage: f64,
+    ///     cores: u32,
+    /// }
+    ///
+    /// let line = "cpu,host=server usage=0.5,cores=8i";
+    /// let cpu: Cpu = LineProtocol::parse_line(line)
+    ///     .unwrap()
+    ///     .fields_into()
+    ///     .unwrap();
+    /// ```
+    pub fn fields_into<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(key, value)| (key.0.clone(), value.clone()));
+
+        let deserializer = MapDeserializer::<_, de::value::Error>::new(fields);
+        let t = T::deserialize(deserializer)?;
+        Ok(t)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Cpu {
+        usage: f64,
+        cores: u32,
+        host: String,
+        online: bool,
+        label: Option<String>,
+    }
+
+    #[test]
+    fn test_de_fields_into() {
+        let line = "cpu usage=0.5,cores=8i,host=\"server\",online=true,unused=1i";
+        let parsed = LineProtocol::parse_line(line).unwrap();
+
+        let result = parsed.fields_into::<Cpu>();
+        assert!(result.is_ok());
+
+        let expected = Cpu {
+            usage: 0.5,
+            cores: 8,
+            host: "server".to_string(),
+            online: true,
+            label: None,
+        };
+        assert_eq!(result.unwrap(), expected)
+    }
+
+    #[test]
+    fn test_de_fields_into_missing_field_is_err() {
+        let line = "cpu usage=0.5,host=\"server\",online=true";
+        let parsed = LineProtocol::parse_line(line).unwrap();
+
+        let result = parsed.fields_into::<Cpu>();
+        assert!(result.is_err())
+    }
+}
@@ -0,0 +1,248 @@
+//! Compare two data points and report how one was modified into the other,
+//! e.g., for audit logging changes made after [LineProtocol::parse_line]
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
+    LineProtocol, Map,
+};
+
+/// The difference between two data points, see [LineProtocol::diff]
+///
+/// Changes are described going from the first data point to the second, e.g.,
+/// an added field is only present in the second data point
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineProtocolDiff {
+    /// The old and new measurement name if it changed
+    pub measurement: Option<(Measurement, Measurement)>,
+
+    /// Tags only present in the new data point
+    pub tags_added: HashMap<TagKey, TagValue>,
+
+    /// Tags only present in the old data point
+    pub tags_removed: HashMap<TagKey, TagValue>,
+
+    /// Tags present in both data points with a different value, as the old and
+    /// new value
+    pub tags_changed: HashMap<TagKey, (TagValue, TagValue)>,
+
+    /// Fields only present in the new data point
+    pub fields_added: HashMap<FieldKey, FieldValue>,
+
+    /// Fields only present in the old data point
+    pub fields_removed: HashMap<FieldKey, FieldValue>,
+
+    /// Fields present in both data points with a different value, as the old
+    /// and new value
+    pub fields_changed: HashMap<FieldKey, (FieldValue, FieldValue)>,
+
+    /// The old and new timestamp if it changed
+    pub timestamp: Option<(Option<i64>, Option<i64>)>,
+}
+
+impl LineProtocolDiff {
+    /// Check if the data points are equal, i.e., nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.measurement.is_none()
+            && self.tags_added.is_empty()
+            && self.tags_removed.is_empty()
+            && self.tags_changed.is_empty()
+            && self.fields_added.is_empty()
+            && self.fields_removed.is_empty()
+            && self.fields_changed.is_empty()
+            && self.timestamp.is_none()
+    }
+}
+
+/// The entries added, removed, and changed going from one map to another
+pub(crate) struct MapDiff<K, V> {
+    pub(crate) added: HashMap<K, V>,
+    pub(crate) removed: HashMap<K, V>,
+    pub(crate) changed: HashMap<K, (V, V)>,
+}
+
+/// Compare two maps by key
+pub(crate) fn diff_maps<K, V>(old: &Map<K, V>, new: &Map<K, V>) -> MapDiff<K, V>
+where
+    K: Clone + Eq + Hash,
+    V: Clone + PartialEq,
+{
+    let mut added = HashMap::new();
+    let mut changed = HashMap::new();
+    for (key, new_value) in new {
+        match old.get(key) {
+            Some(old_value) if old_value == new_value => {}
+            Some(old_value) => {
+                changed.insert(key.clone(), (old_value.clone(), new_value.clone()));
+            }
+            None => {
+                added.insert(key.clone(), new_value.clone());
+            }
+        }
+    }
+
+    let removed = old
+        .iter()
+        .filter(|(key, _)| !new.contains_key(*key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    MapDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+impl LineProtocol {
+    /// Compare the data point with another and report the added, removed, and
+    /// changed tags and fields as well as changes to the measurement name and
+    /// timestamp
+    ///
+    /// A missing tag set is equal to an empty one. The timestamps are compared
+    /// in nanoseconds, but reported as is in their own precision
+    ///
+    /// # Example
+    /// ```rust
+    /// let original = LineProtocol::parse_line(line).unwrap();
+    /// let modified = original.clone().delete_tag("tag").add_field("field2", 1);
+    ///
+    /// let diff = original.diff(&modified);
+    /// for (key, value) in diff.fields_added {
+    ///     println!("added field {key}={value}");
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `other` - The modified data point
+    pub fn diff(&self, other: &LineProtocol) -> LineProtocolDiff {
+        let measurement = (self.measurement != other.measurement)
+            .then(|| (self.measurement.clone(), other.measurement.clone()));
+
+        let empty = Map::new();
+        let tags = diff_maps(
+            self.tags.as_ref().unwrap_or(&empty),
+            other.tags.as_ref().unwrap_or(&empty),
+        );
+        let fields = diff_maps(&self.fields, &other.fields);
+
+        let timestamp = (self.timestamp_nanos() != other.timestamp_nanos())
+            .then_some((self.timestamp, other.timestamp));
+
+        LineProtocolDiff {
+            measurement,
+            tags_added: tags.added,
+            tags_removed: tags.removed,
+            tags_changed: tags.changed,
+            fields_added: fields.added,
+            fields_removed: fields.removed,
+            fields_changed: fields.changed,
+            timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff_empty() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1);
+
+        assert!(line_protocol.diff(&line_protocol.clone()).is_empty());
+
+        // A missing tag set is equal to an empty one
+        let a = LineProtocol::new("measurement").add_field("field", 1);
+        let b = a.clone().add_tag("tag", "value").delete_tag("tag");
+        assert!(a.diff(&b).is_empty());
+    }
+
+    #[test]
+    fn test_diff_changes() {
+        let old = LineProtocol::new("measurement")
+            .add_tag("kept", "value")
+            .add_tag("removed", "value")
+            .add_tag("changed", "old")
+            .add_field("removed", 1)
+            .add_field("changed", 1)
+            .add_field("kept", true)
+            .with_timestamp(1729270461612452700i64);
+
+        let new = LineProtocol::new("renamed")
+            .add_tag("kept", "value")
+            .add_tag("added", "value")
+            .add_tag("changed", "new")
+            .add_field("added", "value")
+            .add_field("changed", 2)
+            .add_field("kept", true);
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert_eq!(
+            diff.measurement,
+            Some((
+                Measurement::from("measurement"),
+                Measurement::from("renamed")
+            ))
+        );
+
+        assert_eq!(
+            diff.tags_added,
+            HashMap::from([(TagKey::from("added"), TagValue::from("value"))])
+        );
+        assert_eq!(
+            diff.tags_removed,
+            HashMap::from([(TagKey::from("removed"), TagValue::from("value"))])
+        );
+        assert_eq!(
+            diff.tags_changed,
+            HashMap::from([(
+                TagKey::from("changed"),
+                (TagValue::from("old"), TagValue::from("new"))
+            )])
+        );
+
+        assert_eq!(
+            diff.fields_added,
+            HashMap::from([(FieldKey::from("added"), FieldValue::from("value"))])
+        );
+        assert_eq!(
+            diff.fields_removed,
+            HashMap::from([(FieldKey::from("removed"), FieldValue::from(1))])
+        );
+        assert_eq!(
+            diff.fields_changed,
+            HashMap::from([(
+                FieldKey::from("changed"),
+                (FieldValue::from(1), FieldValue::from(2))
+            )])
+        );
+
+        assert_eq!(diff.timestamp, Some((Some(1729270461612452700), None)));
+    }
+
+    #[test]
+    fn test_diff_type_change() {
+        let pairs = [
+            (FieldValue::Boolean(true), FieldValue::String("true".into())),
+            (FieldValue::Float(1.0), FieldValue::String("1".into())),
+            (FieldValue::Integer(10), FieldValue::UInteger(10)),
+        ];
+
+        for (old_value, new_value) in pairs {
+            let old = LineProtocol::new("measurement").add_field("field", old_value.clone());
+            let new = LineProtocol::new("measurement").add_field("field", new_value.clone());
+
+            let diff = old.diff(&new);
+            assert!(!diff.is_empty());
+            assert_eq!(
+                diff.fields_changed,
+                HashMap::from([(FieldKey::from("field"), (old_value, new_value))])
+            );
+        }
+    }
+}
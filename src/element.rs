@@ -14,14 +14,23 @@
 //! - field set: Required key value pairs containing the data point data
 //! - timestamp: Optional unix timestamp
 
-use std::{fmt::Display, str::FromStr};
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::OnceLock,
+};
 
 use anyhow::Context;
 use regex::Regex;
 
-use crate::traits::{Convert, Format};
+use crate::{
+    error::ParseError,
+    traits::{Convert, Format},
+};
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Measurement(pub String);
 
 impl From<&str> for Measurement {
@@ -92,9 +101,27 @@ impl Format for Measurement {
     fn unescape(&self) -> Self {
         Measurement(self.0.replace(r"\,", ",").replace(r"\ ", " "))
     }
+
+    fn escape_overhead(&self) -> usize {
+        self.0.matches([' ', ',']).count()
+    }
+
+    fn escape_cow(&self) -> Cow<'_, str> {
+        match self.escape_overhead() {
+            0 => Cow::Borrowed(&self.0),
+            _ => Cow::Owned(self.escape().0),
+        }
+    }
+
+    fn unescape_cow(&self) -> Cow<'_, str> {
+        match self.0.contains('\\') {
+            true => Cow::Owned(self.unescape().0),
+            false => Cow::Borrowed(&self.0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TagKey(pub String);
 
 impl From<&str> for TagKey {
@@ -159,8 +186,11 @@ impl Convert for TagKey {
 
 impl Format for TagKey {
     fn escape(&self) -> Self {
+        // The backslash has to be escaped first to not escape the backslashes
+        // added by the other characters
         TagKey(
             self.0
+                .replace("\\", r"\\")
                 .replace(" ", r"\ ")
                 .replace(",", r"\,")
                 .replace("=", r"\="),
@@ -172,12 +202,31 @@ impl Format for TagKey {
             self.0
                 .replace(r"\=", "=")
                 .replace(r"\,", ",")
-                .replace(r"\ ", " "),
+                .replace(r"\ ", " ")
+                .replace(r"\\", "\\"),
         )
     }
+
+    fn escape_overhead(&self) -> usize {
+        self.0.matches(['\\', ' ', ',', '=']).count()
+    }
+
+    fn escape_cow(&self) -> Cow<'_, str> {
+        match self.escape_overhead() {
+            0 => Cow::Borrowed(&self.0),
+            _ => Cow::Owned(self.escape().0),
+        }
+    }
+
+    fn unescape_cow(&self) -> Cow<'_, str> {
+        match self.0.contains('\\') {
+            true => Cow::Owned(self.unescape().0),
+            false => Cow::Borrowed(&self.0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct TagValue(pub String);
 
 impl From<&str> for TagValue {
@@ -242,8 +291,11 @@ impl Convert for TagValue {
 
 impl Format for TagValue {
     fn escape(&self) -> Self {
+        // The backslash has to be escaped first to not escape the backslashes
+        // added by the other characters
         TagValue(
             self.0
+                .replace("\\", r"\\")
                 .replace(" ", r"\ ")
                 .replace(",", r"\,")
                 .replace("=", r"\="),
@@ -255,12 +307,31 @@ impl Format for TagValue {
             self.0
                 .replace(r"\=", "=")
                 .replace(r"\,", ",")
-                .replace(r"\ ", " "),
+                .replace(r"\ ", " ")
+                .replace(r"\\", "\\"),
         )
     }
+
+    fn escape_overhead(&self) -> usize {
+        self.0.matches(['\\', ' ', ',', '=']).count()
+    }
+
+    fn escape_cow(&self) -> Cow<'_, str> {
+        match self.escape_overhead() {
+            0 => Cow::Borrowed(&self.0),
+            _ => Cow::Owned(self.escape().0),
+        }
+    }
+
+    fn unescape_cow(&self) -> Cow<'_, str> {
+        match self.0.contains('\\') {
+            true => Cow::Owned(self.unescape().0),
+            false => Cow::Borrowed(&self.0),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct FieldKey(pub String);
 
 impl From<&str> for FieldKey {
@@ -341,6 +412,24 @@ impl Format for FieldKey {
                 .replace(r"\ ", " "),
         )
     }
+
+    fn escape_overhead(&self) -> usize {
+        self.0.matches([' ', ',', '=']).count()
+    }
+
+    fn escape_cow(&self) -> Cow<'_, str> {
+        match self.escape_overhead() {
+            0 => Cow::Borrowed(&self.0),
+            _ => Cow::Owned(self.escape().0),
+        }
+    }
+
+    fn unescape_cow(&self) -> Cow<'_, str> {
+        match self.0.contains('\\') {
+            true => Cow::Owned(self.unescape().0),
+            false => Cow::Borrowed(&self.0),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -361,18 +450,259 @@ pub enum FieldValue {
     Boolean(bool),
 }
 
+/// The type of a [FieldValue] without its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    /// A [FieldValue::Float]
+    Float,
+
+    /// A [FieldValue::Integer]
+    Integer,
+
+    /// A [FieldValue::UInteger]
+    UInteger,
+
+    /// A [FieldValue::String]
+    String,
+
+    /// A [FieldValue::Boolean]
+    Boolean,
+}
+
+impl FieldType {
+    /// Get the name of the type, i.e., `float`, `integer`, `uinteger`,
+    /// `string`, or `boolean`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::Float => "float",
+            FieldType::Integer => "integer",
+            FieldType::UInteger => "uinteger",
+            FieldType::String => "string",
+            FieldType::Boolean => "boolean",
+        }
+    }
+}
+
+impl Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FieldValue {
+    /// Get the type of the field value
+    pub fn field_type(&self) -> FieldType {
+        match self {
+            FieldValue::Float(_) => FieldType::Float,
+            FieldValue::Integer(_) => FieldType::Integer,
+            FieldValue::UInteger(_) => FieldType::UInteger,
+            FieldValue::String(_) => FieldType::String,
+            FieldValue::Boolean(_) => FieldType::Boolean,
+        }
+    }
+
+    /// Get the name of the field value's type, see [FieldType::as_str]
+    pub fn type_name(&self) -> &'static str {
+        self.field_type().as_str()
+    }
+
+    /// Check if the field value is a float, integer, or unsigned integer
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            FieldValue::Float(_) | FieldValue::Integer(_) | FieldValue::UInteger(_)
+        )
+    }
+
+    /// Check if the field value is a string
+    pub fn is_string(&self) -> bool {
+        matches!(self, FieldValue::String(_))
+    }
+
+    /// Check if the field value is a boolean
+    pub fn is_boolean(&self) -> bool {
+        matches!(self, FieldValue::Boolean(_))
+    }
+
+    /// Get the value if it is a [FieldValue::Float]
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Float(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a [FieldValue::Integer]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            FieldValue::Integer(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a [FieldValue::UInteger]
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            FieldValue::UInteger(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a [FieldValue::Boolean]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FieldValue::Boolean(boolean) => Some(*boolean),
+            _ => None,
+        }
+    }
+
+    /// Get the value if it is a [FieldValue::String]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Convert the field value to another type where sensible
+    ///
+    /// Numbers convert between each other as long as the value is preserved,
+    /// e.g., a float must be a whole number to convert into an integer. Any
+    /// value converts into a string, a string converts into any type it can
+    /// be parsed as. A boolean only converts to and from a string
+    ///
+    /// `None` is returned if the conversion is not possible
+    ///
+    /// # Example
+    /// ```rust
+    /// let value = FieldValue::Integer(10).convert_to(FieldType::Float);
+    /// assert_eq!(value, Some(FieldValue::Float(10.0)));
+    /// ```
+    ///
+    /// # Args
+    /// * `target` - The type to convert the field value to
+    pub fn convert_to(&self, target: FieldType) -> Option<FieldValue> {
+        // Only whole numbers in range of the integer type convert without losing the
+        // value. The upper bound is exclusive as i64::MAX and u64::MAX round up
+        // to a power of two
+        let whole = |number: f64| number.is_finite() && number.fract() == 0.0;
+
+        let value = match (self, target) {
+            (value, target) if value.field_type() == target => value.clone(),
+
+            (FieldValue::Integer(number), FieldType::Float) => FieldValue::Float(*number as f64),
+            (FieldValue::UInteger(number), FieldType::Float) => FieldValue::Float(*number as f64),
+            (FieldValue::Float(number), FieldType::Integer)
+                if whole(*number) && (i64::MIN as f64..i64::MAX as f64).contains(number) =>
+            {
+                FieldValue::Integer(*number as i64)
+            }
+            (FieldValue::Float(number), FieldType::UInteger)
+                if whole(*number) && (0.0..u64::MAX as f64).contains(number) =>
+            {
+                FieldValue::UInteger(*number as u64)
+            }
+            (FieldValue::Integer(number), FieldType::UInteger) => {
+                FieldValue::UInteger(u64::try_from(*number).ok()?)
+            }
+            (FieldValue::UInteger(number), FieldType::Integer) => {
+                FieldValue::Integer(i64::try_from(*number).ok()?)
+            }
+
+            (FieldValue::Float(number), FieldType::String) => {
+                FieldValue::String(number.to_string())
+            }
+            (FieldValue::Integer(number), FieldType::String) => {
+                FieldValue::String(number.to_string())
+            }
+            (FieldValue::UInteger(number), FieldType::String) => {
+                FieldValue::String(number.to_string())
+            }
+            (FieldValue::Boolean(boolean), FieldType::String) => {
+                FieldValue::String(boolean.to_string())
+            }
+
+            (FieldValue::String(string), FieldType::Float) => {
+                FieldValue::Float(string.trim().parse().ok()?)
+            }
+            (FieldValue::String(string), FieldType::Integer) => {
+                FieldValue::Integer(string.trim().parse().ok()?)
+            }
+            (FieldValue::String(string), FieldType::UInteger) => {
+                FieldValue::UInteger(string.trim().parse().ok()?)
+            }
+            (FieldValue::String(string), FieldType::Boolean) => {
+                FieldValue::Boolean(string.trim().parse().ok()?)
+            }
+
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    /// Infer the field value type from a string
+    ///
+    /// Performs the same type guessing as [Convert::parse_from], e.g., `"10i"`
+    /// is inferred as [FieldValue::Integer] and `"true"` as
+    /// [FieldValue::Boolean]. Unlike [Convert::parse_from] this can't fail, an
+    /// integer which does not fit in its type is inferred as a
+    /// [FieldValue::String]
+    ///
+    /// This is different from the [From] implementations for strings which
+    /// always produce a [FieldValue::String] with the string as is
+    ///
+    /// # Example
+    /// ```rust
+    /// assert_eq!(FieldValue::infer("true"), FieldValue::Boolean(true));
+    /// assert_eq!(
+    ///     FieldValue::from("true"),
+    ///     FieldValue::String("true".to_string())
+    /// );
+    /// ```
+    ///
+    /// # Args
+    /// * `s` - The string to infer the field value from
+    pub fn infer(s: &str) -> FieldValue {
+        FieldValue::parse_from(s).unwrap_or_else(|_| FieldValue::String(s.to_string()))
+    }
+}
+
+/// Infers the field value type the same way as [Convert::parse_from], e.g.,
+/// `"true".parse::<FieldValue>()` is a [FieldValue::Boolean] while
+/// `FieldValue::from("true")` is a [FieldValue::String]
+///
+/// # Example
+/// ```rust
+/// let value: FieldValue = "10i".parse().unwrap();
+/// // Output: FieldValue::Integer(10)
+/// ```
+impl FromStr for FieldValue {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FieldValue::parse_from(s).map_err(ParseError::from_value_error)
+    }
+}
+
+/// Always produces a [FieldValue::String], see [FieldValue::infer] or the
+/// [FromStr] implementation for inferring the type from the string
 impl From<&str> for FieldValue {
     fn from(value: &str) -> Self {
         FieldValue::String(value.to_owned())
     }
 }
 
+/// Always produces a [FieldValue::String], see [FieldValue::infer] for
+/// inferring the type from the string
 impl From<&String> for FieldValue {
     fn from(value: &String) -> Self {
         FieldValue::String(value.to_owned())
     }
 }
 
+/// Always produces a [FieldValue::String], see [FieldValue::infer] for
+/// inferring the type from the string
 impl From<String> for FieldValue {
     fn from(value: String) -> Self {
         FieldValue::String(value)
@@ -445,13 +775,33 @@ impl From<bool> for FieldValue {
     }
 }
 
+impl TryFrom<i128> for FieldValue {
+    type Error = std::num::TryFromIntError;
+
+    /// Convert an `i128` into an integer field value, failing if it does not
+    /// fit in an `i64`
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        Ok(FieldValue::Integer(i64::try_from(value)?))
+    }
+}
+
+impl TryFrom<u128> for FieldValue {
+    type Error = std::num::TryFromIntError;
+
+    /// Convert a `u128` into an unsigned integer field value, failing if it
+    /// does not fit in a `u64`
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        Ok(FieldValue::UInteger(u64::try_from(value)?))
+    }
+}
+
 impl Display for FieldValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let value = match self {
             FieldValue::Float(number) => format!("{number}"),
             FieldValue::Integer(number) => format!("{number}i"),
-            FieldValue::UInteger(number) => format!("{number}i"),
-            FieldValue::String(string) => format!("{string}"),
+            FieldValue::UInteger(number) => format!("{number}u"),
+            FieldValue::String(string) => string.to_string(),
             FieldValue::Boolean(boolean) => format!("{boolean}"),
         };
 
@@ -459,9 +809,37 @@ impl Display for FieldValue {
     }
 }
 
+/// Two field values are equal if they are of the same type and hold the same
+/// value, e.g., `Boolean(true)` is not equal to `String("true")`
+///
+/// Floats are compared by their bit pattern, meaning `NaN` is equal to itself
+/// and `0.0` is not equal to `-0.0`. This keeps the comparison consistent with
+/// [Hash]
 impl PartialEq for FieldValue {
     fn eq(&self, other: &Self) -> bool {
-        self.to_string() == other.to_string()
+        match (self, other) {
+            (FieldValue::Float(a), FieldValue::Float(b)) => a.to_bits() == b.to_bits(),
+            (FieldValue::Integer(a), FieldValue::Integer(b)) => a == b,
+            (FieldValue::UInteger(a), FieldValue::UInteger(b)) => a == b,
+            (FieldValue::String(a), FieldValue::String(b)) => a == b,
+            (FieldValue::Boolean(a), FieldValue::Boolean(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for FieldValue {}
+
+impl Hash for FieldValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            FieldValue::Float(number) => number.to_bits().hash(state),
+            FieldValue::Integer(number) => number.hash(state),
+            FieldValue::UInteger(number) => number.hash(state),
+            FieldValue::String(string) => string.hash(state),
+            FieldValue::Boolean(boolean) => boolean.hash(state),
+        }
     }
 }
 
@@ -481,30 +859,35 @@ impl Convert for FieldValue {
         let s = from.to_string();
 
         // Check if string is a number that ends with an i
-        let re = Regex::new(r"^-?\d+i$").unwrap();
+        static INTEGER: OnceLock<Regex> = OnceLock::new();
+        let re = INTEGER.get_or_init(|| Regex::new(r"^-?\d+i$").unwrap());
         if re.is_match(&s) {
             // Remove the `i`
             let mut number = s.to_string();
             number.pop();
 
-            let value = match number.starts_with("-") {
-                true => {
-                    let int = number
-                        .parse::<i64>()
-                        .with_context(|| format!("number {s} is not a valid integer"))?;
+            // The regex guarantees a number, meaning it can only fail by not fitting. The
+            // `i` suffix means signed so it is never treated as an unsigned integer
+            let int = number
+                .parse::<i64>()
+                .map_err(|_| ParseError::IntegerOverflow(s.clone()))?;
 
-                    FieldValue::Integer(int)
-                }
-                false => {
-                    let uint = number
-                        .parse::<u64>()
-                        .with_context(|| format!("number {s} is not a valid unsigned integer"))?;
+            return Ok(FieldValue::Integer(int));
+        };
 
-                    FieldValue::UInteger(uint)
-                }
-            };
+        // Check if string is a number that ends with an u
+        static UINTEGER: OnceLock<Regex> = OnceLock::new();
+        let re = UINTEGER.get_or_init(|| Regex::new(r"^\d+u$").unwrap());
+        if re.is_match(&s) {
+            // Remove the `u`
+            let mut number = s.to_string();
+            number.pop();
 
-            return Ok(value);
+            let uint = number
+                .parse::<u64>()
+                .with_context(|| format!("number {s} is not a valid unsigned integer"))?;
+
+            return Ok(FieldValue::UInteger(uint));
         };
 
         // Check if string is a float or just a regular number without and `i`
@@ -522,6 +905,48 @@ impl Convert for FieldValue {
         Ok(value)
     }
 
+    /// Attempt to parse a raw field value as written in line protocol into a
+    /// [FieldValue]
+    ///
+    /// Booleans are the unquoted tokens listed by the line protocol
+    /// specification, i.e., `t`, `T`, `true`, `True`, `TRUE` and their `false`
+    /// counterparts. Unlike [Convert::parse_from] a string must be quoted,
+    /// meaning a quoted `"T"` stays a string and an unquoted value which is not
+    /// a number or boolean returns [ParseError::UnquotedString]. Non-finite
+    /// floats, e.g., `NaN` or `inf`, are not line protocol and return
+    /// [ParseError::NonFiniteFloat]
+    ///
+    /// The [From] implementations for strings are unaffected, they always
+    /// produce a [FieldValue::String]
+    ///
+    /// # Example
+    /// ```rust
+    /// let value = FieldValue::parse_from_strict("t").unwrap();
+    /// // Output: FieldValue::Boolean(true)
+    /// let value = FieldValue::parse_from_strict("\"T\"").unwrap().unescape();
+    /// // Output: FieldValue::String("T")
+    /// ```
+    fn parse_from_strict<T>(from: T) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+        T: ToString,
+    {
+        let s = from.to_string();
+
+        // Quoted strings are kept as is to be unescaped
+        if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+            return Ok(FieldValue::String(s));
+        }
+
+        match FieldValue::parse_from(&s)? {
+            FieldValue::String(_) => Err(ParseError::UnquotedString(s).into()),
+            FieldValue::Float(number) if !number.is_finite() => {
+                Err(ParseError::NonFiniteFloat(s).into())
+            }
+            value => Ok(value),
+        }
+    }
+
     /// Attempt to parse [FieldValue] into generic type T
     ///
     /// Note: This only makes sense to do if type is [FieldValue::String]
@@ -549,6 +974,8 @@ impl Convert for FieldValue {
     }
 }
 
+/// A string field value is quoted with its backslashes and double quotes
+/// escaped, the only escapes line protocol recognizes in a string field value
 impl Format for FieldValue {
     fn escape(&self) -> Self {
         match self {
@@ -563,15 +990,89 @@ impl Format for FieldValue {
     fn unescape(&self) -> Self {
         match self {
             FieldValue::String(string) => {
-                let unescaped = match string.starts_with("\"") && string.ends_with("\"") {
-                    true => &string[1..string.len() - 1],
-                    false => string.as_str(),
-                };
-                FieldValue::String(unescaped.replace("\\\"", "\"").replace("\\\\", "\\"))
+                let unquoted =
+                    match string.len() >= 2 && string.starts_with("\"") && string.ends_with("\"") {
+                        true => &string[1..string.len() - 1],
+                        false => string.as_str(),
+                    };
+
+                // Unescape in a single pass so a sequence such as \\" (escaped slash
+                // followed by a quote) is not unescaped twice. Only a slash or a double
+                // quote can be escaped, any other slash is kept as is
+                let mut unescaped = String::with_capacity(unquoted.len());
+                let mut chars = unquoted.chars().peekable();
+                while let Some(char) = chars.next() {
+                    if char == '\\' {
+                        if let Some(next @ ('\\' | '"')) = chars.peek().copied() {
+                            unescaped.push(next);
+                            chars.next();
+                            continue;
+                        }
+                    }
+
+                    unescaped.push(char);
+                }
+
+                FieldValue::String(unescaped)
             }
             other => other.clone(),
         }
     }
+
+    fn escape_overhead(&self) -> usize {
+        match self {
+            // Escaped characters plus the surrounding double quotes
+            FieldValue::String(string) => string.matches(['\\', '"']).count() + 2,
+            _ => 0,
+        }
+    }
+
+    fn escape_cow(&self) -> Cow<'_, str> {
+        // A string field value is always quoted and other field values have no string
+        // to borrow from
+        Cow::Owned(self.escape().to_string())
+    }
+
+    fn unescape_cow(&self) -> Cow<'_, str> {
+        match self {
+            FieldValue::String(string) if !string.contains('\\') => {
+                match string.len() >= 2 && string.starts_with("\"") && string.ends_with("\"") {
+                    true => Cow::Borrowed(&string[1..string.len() - 1]),
+                    false => Cow::Borrowed(string),
+                }
+            }
+            other => Cow::Owned(other.unescape().to_string()),
+        }
+    }
+}
+
+/// The part of a data point an [Attribute] originates from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttrKind {
+    /// A tag key value pair
+    Tag,
+
+    /// A field key value pair
+    Field,
+
+    /// The timestamp, the key is always `timestamp`
+    Timestamp,
+}
+
+/// A uniform view of a tag, field, or timestamp of a data point
+///
+/// The key and value are unescaped. A field value is formatted as its
+/// [Display] implementation, e.g., an integer is `10i`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attribute {
+    /// The part of the data point the attribute originates from
+    pub kind: AttrKind,
+
+    /// The tag key, field key, or `timestamp`
+    pub key: String,
+
+    /// The tag value, field value, or timestamp
+    pub value: String,
 }
 
 #[cfg(test)]
@@ -600,6 +1101,35 @@ mod test {
         assert_eq!(unescaped_value.to_string(), "some, value=");
     }
 
+    #[test]
+    fn test_tag_key_escape_unescape_backslash() {
+        let key = TagKey::from(r"C:\path, to=");
+        let escaped_key = key.escape();
+
+        assert_eq!(escaped_key.to_string(), r"C:\\path\,\ to\=");
+
+        let unescaped_key = escaped_key.unescape();
+        assert_eq!(unescaped_key, key);
+    }
+
+    #[test]
+    fn test_tag_value_escape_unescape_backslash() {
+        let value = TagValue::from(r"C:\path, to=");
+        let escaped_value = value.escape();
+
+        assert_eq!(escaped_value.to_string(), r"C:\\path\,\ to\=");
+
+        let unescaped_value = escaped_value.unescape();
+        assert_eq!(unescaped_value, value);
+
+        // A trailing backslash followed by an escaped character
+        let value = TagValue::from(r"value\,");
+        let escaped_value = value.escape();
+
+        assert_eq!(escaped_value.to_string(), r"value\\\,");
+        assert_eq!(escaped_value.unescape(), value);
+    }
+
     #[test]
     fn test_field_key_escape_unescape() {
         let key = FieldKey::from("some, value=");
@@ -626,6 +1156,55 @@ mod test {
         assert_eq!(unescaped_value.to_string(), "{\"foo\": [\"bar=\\baz\"]}");
     }
 
+    #[test]
+    fn test_escape_overhead() {
+        assert_eq!(Measurement::from("measurement").escape_overhead(), 0);
+        assert_eq!(Measurement::from("my measurement,").escape_overhead(), 2);
+        assert_eq!(TagKey::from("some, value=").escape_overhead(), 3);
+        assert_eq!(TagValue::from("some, value=").escape_overhead(), 3);
+        assert_eq!(FieldKey::from("key").escape_overhead(), 0);
+        assert_eq!(FieldValue::from("value").escape_overhead(), 2);
+        assert_eq!(FieldValue::from(10).escape_overhead(), 0);
+
+        let values = vec![
+            FieldValue::from("{\"foo\": [\"bar=\\baz\"]}"),
+            FieldValue::from(""),
+            FieldValue::from(true),
+        ];
+        for value in values {
+            let overhead = value.escape().to_string().len() - value.to_string().len();
+            assert_eq!(value.escape_overhead(), overhead);
+        }
+
+        let key = TagKey::from("some, value=");
+        assert_eq!(key.escape_overhead(), key.escape().0.len() - key.0.len());
+    }
+
+    #[test]
+    fn test_field_value_from_is_literal_and_infer_guesses() {
+        assert_eq!(
+            FieldValue::from("true"),
+            FieldValue::String("true".to_string())
+        );
+        assert_eq!(FieldValue::infer("true"), FieldValue::Boolean(true));
+
+        assert_eq!(
+            FieldValue::from("10i"),
+            FieldValue::String("10i".to_string())
+        );
+        assert!(matches!(FieldValue::infer("10i"), FieldValue::Integer(10)));
+        assert!(matches!(
+            FieldValue::infer("-10i"),
+            FieldValue::Integer(-10)
+        ));
+        assert!(matches!(FieldValue::infer("0.5"), FieldValue::Float(_)));
+        assert!(matches!(FieldValue::infer("hello"), FieldValue::String(_)));
+
+        // An integer which does not fit is kept as a string
+        let overflow = "-9223372036854775809i";
+        assert!(matches!(FieldValue::infer(overflow), FieldValue::String(s) if s == overflow));
+    }
+
     #[test]
     fn test_field_value_parse_float() {
         let parsed = FieldValue::parse_from("10.0").unwrap();
@@ -646,10 +1225,24 @@ mod test {
 
     #[test]
     fn test_field_value_parse_unsigned_integer() {
-        // Only if a number cannot fit in an i64 it will parsed into a u64
-        let parsed = FieldValue::parse_from("9223372036854775808i").unwrap();
-        let expected = FieldValue::UInteger(9223372036854775808);
+        let parsed = FieldValue::parse_from("10u").unwrap();
+        let expected = FieldValue::UInteger(10);
         assert_eq!(parsed, expected);
+
+        let parsed = FieldValue::parse_from("18446744073709551615u").unwrap();
+        let expected = FieldValue::UInteger(u64::MAX);
+        assert_eq!(parsed, expected);
+
+        // An `i` suffix is always a signed integer, even if it would fit in a u64
+        let parsed = FieldValue::parse_from("9223372036854775808i");
+        assert!(matches!(
+            parsed.unwrap_err().downcast::<ParseError>(),
+            Ok(ParseError::IntegerOverflow(s)) if s == "9223372036854775808i"
+        ));
+
+        // A negative number cannot be an unsigned integer
+        let parsed = FieldValue::parse_from("-10u").unwrap();
+        assert_eq!(parsed, FieldValue::String("-10u".to_string()));
     }
 
     #[test]
@@ -674,9 +1267,242 @@ mod test {
         assert_eq!(FieldValue::Float(10.0).to_string(), "10");
         assert_eq!(FieldValue::Float(10.5).to_string(), "10.5");
         assert_eq!(FieldValue::Integer(10).to_string(), "10i");
-        assert_eq!(FieldValue::UInteger(10).to_string(), "10i");
+        assert_eq!(FieldValue::UInteger(10).to_string(), "10u");
         assert_eq!(FieldValue::String("hello".to_string()).to_string(), "hello");
         assert_eq!(FieldValue::Boolean(true).to_string(), "true");
         assert_eq!(FieldValue::Boolean(false).to_string(), "false");
     }
+
+    #[test]
+    fn test_field_value_escape_unescape_lossless() {
+        let strings = vec![
+            "line\\nbreak",
+            "tab\\tchar",
+            "back\\\\slash",
+            "quote\\\"d",
+            "\\",
+            "\\\\",
+            "\"",
+            "\\\"",
+            "ends with\\",
+            "C:\\path\\to\\file",
+            "{\"json\": \"a\\nb\\t\\\"c\\\"\"}",
+            "mixed \\\" and \\\\\" and \"\\",
+            "real\nnewline and\ttab",
+        ];
+
+        for string in strings {
+            let value = FieldValue::from(string);
+            assert_eq!(value.escape().unescape(), value, "round trip of {string:?}");
+        }
+    }
+
+    #[test]
+    fn test_field_value_unescape_single_quote() {
+        let value = FieldValue::String("\"".to_string());
+        assert_eq!(value.unescape().to_string(), "\"");
+    }
+
+    #[test]
+    fn test_field_value_convert_to() {
+        let cases = [
+            (
+                FieldValue::Integer(-10),
+                FieldType::Float,
+                Some(FieldValue::Float(-10.0)),
+            ),
+            (
+                FieldValue::Float(10.0),
+                FieldType::Integer,
+                Some(FieldValue::Integer(10)),
+            ),
+            (FieldValue::Float(10.5), FieldType::Integer, None),
+            (FieldValue::Float(-1.0), FieldType::UInteger, None),
+            (FieldValue::Float(9.3e18), FieldType::Integer, None),
+            (FieldValue::Integer(-1), FieldType::UInteger, None),
+            (
+                FieldValue::UInteger(10),
+                FieldType::Integer,
+                Some(FieldValue::Integer(10)),
+            ),
+            (
+                FieldValue::Integer(10),
+                FieldType::String,
+                Some(FieldValue::String("10".to_string())),
+            ),
+            (
+                FieldValue::String("true".to_string()),
+                FieldType::Boolean,
+                Some(FieldValue::Boolean(true)),
+            ),
+            (
+                FieldValue::String("hello".to_string()),
+                FieldType::Integer,
+                None,
+            ),
+            (FieldValue::Boolean(true), FieldType::Integer, None),
+        ];
+
+        for (value, target, expected) in cases {
+            assert_eq!(value.convert_to(target), expected, "{value:?} to {target}");
+        }
+    }
+
+    #[test]
+    fn test_escape_cow_borrows_unchanged() {
+        let value = TagValue::from("us-east-1");
+        assert!(matches!(value.escape_cow(), Cow::Borrowed("us-east-1")));
+        assert!(matches!(value.unescape_cow(), Cow::Borrowed("us-east-1")));
+
+        let value = TagValue::from("us east,1");
+        assert!(matches!(value.escape_cow(), Cow::Owned(_)));
+        assert_eq!(value.escape_cow(), value.escape().0);
+
+        let escaped = value.escape();
+        assert!(matches!(escaped.unescape_cow(), Cow::Owned(_)));
+        assert_eq!(escaped.unescape_cow(), value.0);
+
+        let measurement = Measurement::from("measurement");
+        assert!(matches!(measurement.escape_cow(), Cow::Borrowed(_)));
+
+        let value = FieldValue::String("\"hello\"".to_string());
+        assert!(matches!(value.unescape_cow(), Cow::Borrowed("hello")));
+        assert_eq!(
+            FieldValue::String("hello".to_string()).escape_cow(),
+            "\"hello\""
+        );
+    }
+
+    #[test]
+    fn test_element_field_value_accessors() {
+        assert_eq!(FieldValue::Float(0.5).as_f64(), Some(0.5));
+        assert_eq!(FieldValue::Integer(-10).as_i64(), Some(-10));
+        assert_eq!(FieldValue::UInteger(10).as_u64(), Some(10));
+        assert_eq!(FieldValue::Boolean(true).as_bool(), Some(true));
+        assert_eq!(FieldValue::String("value".into()).as_str(), Some("value"));
+
+        // No coercion between the types
+        assert_eq!(FieldValue::Integer(10).as_f64(), None);
+        assert_eq!(FieldValue::Float(10.0).as_i64(), None);
+        assert_eq!(FieldValue::Integer(10).as_u64(), None);
+        assert_eq!(FieldValue::String("true".into()).as_bool(), None);
+        assert_eq!(FieldValue::Boolean(true).as_str(), None);
+    }
+
+    #[test]
+    fn test_element_field_value_type_name() {
+        let values = [
+            (FieldValue::Float(0.5), "float"),
+            (FieldValue::Integer(-10), "integer"),
+            (FieldValue::UInteger(10), "uinteger"),
+            (FieldValue::String("value".into()), "string"),
+            (FieldValue::Boolean(true), "boolean"),
+        ];
+
+        for (value, expected) in values {
+            assert_eq!(value.type_name(), expected);
+            assert_eq!(value.field_type().to_string(), expected);
+            assert_eq!(
+                value.is_numeric(),
+                !value.is_string() && !value.is_boolean()
+            );
+        }
+
+        assert!(FieldValue::String("10".into()).is_string());
+        assert!(!FieldValue::String("10".into()).is_numeric());
+        assert!(FieldValue::Boolean(false).is_boolean());
+    }
+
+    #[test]
+    fn test_element_field_value_try_from_wide() {
+        assert_eq!(
+            FieldValue::try_from(-10i128).unwrap(),
+            FieldValue::Integer(-10)
+        );
+        assert_eq!(
+            FieldValue::try_from(i64::MIN as i128).unwrap(),
+            FieldValue::Integer(i64::MIN)
+        );
+        assert!(FieldValue::try_from(i64::MAX as i128 + 1).is_err());
+        assert!(FieldValue::try_from(i64::MIN as i128 - 1).is_err());
+
+        assert_eq!(
+            FieldValue::try_from(u64::MAX as u128).unwrap(),
+            FieldValue::UInteger(u64::MAX)
+        );
+        assert!(FieldValue::try_from(u64::MAX as u128 + 1).is_err());
+    }
+
+    #[test]
+    fn test_element_field_value_parse_strict() {
+        let values = [
+            ("t", FieldValue::Boolean(true)),
+            ("T", FieldValue::Boolean(true)),
+            ("true", FieldValue::Boolean(true)),
+            ("True", FieldValue::Boolean(true)),
+            ("TRUE", FieldValue::Boolean(true)),
+            ("f", FieldValue::Boolean(false)),
+            ("F", FieldValue::Boolean(false)),
+            ("false", FieldValue::Boolean(false)),
+            ("False", FieldValue::Boolean(false)),
+            ("FALSE", FieldValue::Boolean(false)),
+            ("10i", FieldValue::Integer(10)),
+            ("10u", FieldValue::UInteger(10)),
+            ("0.5", FieldValue::Float(0.5)),
+            ("\"T\"", FieldValue::String("T".to_string())),
+            ("\"true\"", FieldValue::String("true".to_string())),
+        ];
+
+        for (raw, expected) in values {
+            let parsed = FieldValue::parse_from_strict(raw).unwrap().unescape();
+            assert_eq!(parsed, expected, "{raw}");
+        }
+
+        for raw in ["tRUE", "yes", "value"] {
+            let error = FieldValue::parse_from_strict(raw).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<ParseError>(),
+                Some(ParseError::UnquotedString(_))
+            ));
+        }
+
+        for raw in ["NaN", "inf", "-infinity"] {
+            let error = FieldValue::parse_from_strict(raw).unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<ParseError>(),
+                Some(ParseError::NonFiniteFloat(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_element_field_value_from_str() {
+        assert_eq!(
+            "true".parse::<FieldValue>().unwrap(),
+            FieldValue::Boolean(true)
+        );
+        assert_eq!(
+            "10i".parse::<FieldValue>().unwrap(),
+            FieldValue::Integer(10)
+        );
+        assert_eq!(
+            "10u".parse::<FieldValue>().unwrap(),
+            FieldValue::UInteger(10)
+        );
+        assert_eq!("0.5".parse::<FieldValue>().unwrap(), FieldValue::Float(0.5));
+        assert_eq!(
+            "value".parse::<FieldValue>().unwrap(),
+            FieldValue::String("value".to_string())
+        );
+        assert!(matches!(
+            "9223372036854775808i".parse::<FieldValue>(),
+            Err(ParseError::IntegerOverflow(_))
+        ));
+
+        // Converting a string never infers the type
+        assert_eq!(
+            FieldValue::from("true"),
+            FieldValue::String("true".to_string())
+        );
+    }
 }
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::element::{FieldKey, FieldType, Measurement, TagKey};
+
 pub(crate) type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
 pub(crate) type Result<T> = std::result::Result<T, LineProtocolError>;
 
@@ -8,8 +10,8 @@ pub enum BuilderError {
     #[error("measurement name cannot be empty")]
     EmptyMeasurement,
 
-    #[error("measurement name cannot start with '_' (underscore)")]
-    InvalidMeasurement,
+    #[error("measurement name cannot {0}")]
+    InvalidMeasurement(&'static str),
 
     #[error("tag key cannot be empty")]
     EmptyTagKey,
@@ -20,6 +22,21 @@ pub enum BuilderError {
     #[error("tag value cannot be empty")]
     EmptyTagValue,
 
+    #[error("tag set is {bytes} bytes which exceeds the maximum of {max} bytes")]
+    TagsTooLarge { bytes: usize, max: usize },
+
+    #[error("data point has {count} tags which exceeds the maximum of {max} tags")]
+    TooManyTags { count: usize, max: usize },
+
+    #[error("data point has {count} fields which exceeds the maximum of {max} fields")]
+    TooManyFields { count: usize, max: usize },
+
+    #[error("{key} is {len} bytes which exceeds the maximum key length of {max} bytes")]
+    KeyTooLong { key: String, len: usize, max: usize },
+
+    #[error("line is {bytes} bytes which exceeds the maximum of {max} bytes")]
+    LineTooLarge { bytes: usize, max: usize },
+
     #[error("key cannot be empty")]
     EmptyFieldKey,
 
@@ -29,8 +46,43 @@ pub enum BuilderError {
     #[error("value cannot be empty")]
     EmptyFieldValue,
 
+    #[error("invalid value for field {key}: {reason}")]
+    InvalidFieldValue { key: String, reason: &'static str },
+
+    #[error("field {key} is a non-finite float, NaN and infinity are not supported")]
+    NonFiniteFloat { key: String },
+
     #[error("atleast one field is required")]
     MissingFields,
+
+    #[error("field {0} does not exist")]
+    UnknownField(String),
+
+    #[error("field {key} cannot be converted from {from} to {to}")]
+    FieldConversion {
+        key: String,
+        from: FieldType,
+        to: FieldType,
+    },
+
+    #[error("timestamp {0} cannot be represented in the requested precision")]
+    TimestampOutOfRange(i64),
+
+    #[error("date {0} cannot be represented as a timestamp in nanoseconds")]
+    DateTimeOutOfRange(String),
+
+    #[error("json {0} cannot be used as a field value")]
+    UnsupportedJsonValue(&'static str),
+
+    #[error("error on data point {index}: {source}")]
+    AtPoint {
+        index: usize,
+        #[source]
+        source: BoxError,
+    },
+
+    #[error("field key and value columns differ in length ({keys} keys, {values} values)")]
+    ColumnLengthMismatch { keys: usize, values: usize },
 }
 
 #[derive(Debug, Error)]
@@ -41,6 +93,12 @@ pub enum ParseError {
     #[error("line cannot be empty")]
     EmptyLine,
 
+    #[error("line is not valid UTF-8: {0}")]
+    InvalidUtf8(#[from] std::str::Utf8Error),
+
+    #[error("line contains a carriage return (\\r) which is not part of a line ending")]
+    StrayCarriageReturn,
+
     #[error("measurement name cannot be empty")]
     MissingMeasurement,
 
@@ -50,10 +108,99 @@ pub enum ParseError {
     #[error("timestamp is not a valid number")]
     InvalidTimestamp,
 
+    #[error("unexpected data after the timestamp: {0}")]
+    TrailingData(String),
+
+    #[error("field key `time` is not allowed")]
+    TimeField,
+
+    #[error("key {0} is defined more than once")]
+    DuplicateKey(String),
+
+    #[error("data point duplicates the data point on line {0}")]
+    DuplicatePoint(usize),
+
+    #[error("integer {0} does not fit in a signed 64-bit integer")]
+    IntegerOverflow(String),
+
+    #[error("field value {0} is not a number or boolean, string field values must be quoted")]
+    UnquotedString(String),
+
+    #[error("float {0} is not finite, NaN and infinity are not supported")]
+    NonFiniteFloat(String),
+
     #[error("invalid set: {0}")]
     InvalidSet(#[source] BoxError),
+
+    #[error("failed to read line: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("error on line {line}: {source}")]
+    AtLine {
+        line: usize,
+        #[source]
+        source: BoxError,
+    },
+}
+
+impl ParseError {
+    /// Wrap an error from parsing a set value, keeping a [ParseError] as is
+    pub(crate) fn from_value_error(e: anyhow::Error) -> ParseError {
+        match e.downcast::<ParseError>() {
+            Ok(e) => e,
+            Err(e) => ParseError::InvalidSet(e.into()),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("input ended unexpectedly")]
+    UnexpectedEnd,
+
+    #[error("string is not valid UTF-8")]
+    InvalidUtf8,
+
+    #[error("invalid marker byte {0}")]
+    InvalidMarker(u8),
+
+    #[error("input contains {0} trailing bytes")]
+    TrailingBytes(usize),
+}
+
+/// The error returned when a batch expected to contain a single measurement
+/// does not
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("expected a single measurement, found [{}]", measurements.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", "))]
+pub struct MixedMeasurements {
+    /// The distinct measurements found in the order they first appear. Empty
+    /// if the batch is empty
+    pub measurements: Vec<Measurement>,
+}
+
+/// A way a data point does not conform to a [Schema](crate::schema::Schema)
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SchemaViolation {
+    #[error("required tag {0} is missing")]
+    MissingTag(TagKey),
+
+    #[error("required field {0} is missing")]
+    MissingField(FieldKey),
+
+    #[error("field {key} is expected to be a {expected} but is a {found}")]
+    TypeMismatch {
+        key: FieldKey,
+        expected: FieldType,
+        found: FieldType,
+    },
 }
 
+/// The error returned by the builder and parser methods
+///
+/// The underlying cause is accessible by walking the chain of
+/// [source](std::error::Error::source) errors, e.g., a [ParseError::InvalidSet]
+/// caused by a field value failing to convert exposes the conversion error as
+/// its source
 #[derive(Debug, Error)]
 pub enum LineProtocolError {
     #[error("A builder error occured: {0}")]
@@ -61,4 +208,11 @@ pub enum LineProtocolError {
 
     #[error("A parser error occured: {0}")]
     ParserError(#[from] ParseError),
+
+    #[error("A decode error occured: {0}")]
+    DecodeError(#[from] DecodeError),
+
+    #[cfg(feature = "serde")]
+    #[error("A deserialization error occured: {0}")]
+    DeserializeError(#[from] serde::de::value::Error),
 }
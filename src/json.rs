@@ -0,0 +1,249 @@
+//! Conversion from [serde_json] values into field values
+//!
+//! Requires the `json` feature
+
+use serde_json::{Map, Value};
+
+use crate::{
+    element::FieldValue,
+    error::{BuilderError, Result},
+    LineProtocol,
+};
+
+/// Options used to change how a JSON object is added as fields, see
+/// [LineProtocol::add_json_fields_with]
+///
+/// By default nested objects, arrays and `null` members are skipped
+///
+/// # Example
+/// ```rust
+/// let options = JsonOptions::new().flatten_nested(true);
+/// let line_protocol = line_protocol
+///     .add_json_fields_with(&object, &options)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonOptions {
+    flatten_nested: bool,
+    strict: bool,
+}
+
+impl JsonOptions {
+    /// Create a new set of JSON options with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether nested objects are flattened into fields with dotted keys,
+    /// e.g., `{"cpu": {"user": 1}}` becomes the field `cpu.user=1i`. Disabled
+    /// by default
+    ///
+    /// # Args
+    /// * `enabled` - Whether nested objects are flattened
+    pub fn flatten_nested(mut self, enabled: bool) -> Self {
+        self.flatten_nested = enabled;
+        self
+    }
+
+    /// Set whether members which cannot be added as a field return an error
+    /// instead of being skipped. Disabled by default
+    ///
+    /// # Args
+    /// * `enabled` - Whether unsupported members return an error
+    pub fn strict(mut self, enabled: bool) -> Self {
+        self.strict = enabled;
+        self
+    }
+}
+
+impl LineProtocol {
+    /// Add one field per scalar member of a JSON object
+    ///
+    /// Nested objects, arrays and `null` members are skipped. Use
+    /// [LineProtocol::add_json_fields_with] to flatten nested objects or to
+    /// return an error instead
+    ///
+    /// # Example
+    /// ```rust
+    /// let payload = serde_json::json!({ "temperature": 21.5, "status": "ok" });
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_json_fields(payload.as_object().unwrap());
+    /// // Output: measurement status="ok",temperature=21.5
+    /// ```
+    ///
+    /// # Args
+    /// * `obj` - The JSON object to add fields from
+    pub fn add_json_fields(mut self, obj: &Map<String, Value>) -> Self {
+        // Unsupported members are skipped when not strict so this cannot fail
+        let _ = self.add_json_members(None, obj, &JsonOptions::new());
+        self
+    }
+
+    /// Add one field per scalar member of a JSON object using the given
+    /// options
+    ///
+    /// # Example
+    /// ```rust
+    /// let payload = serde_json::json!({ "cpu": { "user": 10, "system": 5 } });
+    /// let options = JsonOptions::new().flatten_nested(true).strict(true);
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_json_fields_with(payload.as_object().unwrap(), &options)
+    ///     .unwrap();
+    /// // Output: measurement cpu.system=5i,cpu.user=10i
+    /// ```
+    ///
+    /// # Args
+    /// * `obj` - The JSON object to add fields from
+    /// * `options` - Options deciding how nested and unsupported members are
+    ///   handled
+    pub fn add_json_fields_with(
+        mut self,
+        obj: &Map<String, Value>,
+        options: &JsonOptions,
+    ) -> Result<Self> {
+        self.add_json_members(None, obj, options)?;
+        Ok(self)
+    }
+
+    fn add_json_members(
+        &mut self,
+        prefix: Option<&str>,
+        obj: &Map<String, Value>,
+        options: &JsonOptions,
+    ) -> Result<()> {
+        for (key, value) in obj {
+            let key = match prefix {
+                Some(prefix) => format!("{prefix}.{key}"),
+                None => key.clone(),
+            };
+
+            match value {
+                Value::Object(nested) if options.flatten_nested => {
+                    self.add_json_members(Some(&key), nested, options)?;
+                }
+                value => match FieldValue::try_from(value.clone()) {
+                    Ok(value) => self.add_field_ref(key, value),
+                    Err(error) if options.strict => return Err(error.into()),
+                    Err(_) => {}
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<Value> for FieldValue {
+    type Error = BuilderError;
+
+    /// Convert a scalar JSON value into a field value
+    ///
+    /// Numbers become an integer if they fit in an `i64`, an unsigned integer
+    /// if they only fit in an `u64` and a float otherwise. Booleans and strings
+    /// map to their respective field value
+    ///
+    /// `null`, arrays and objects have no field value equivalent and return an
+    /// error. Objects must be flattened into separate fields by the caller
+    ///
+    /// # Example
+    /// ```rust
+    /// let value = FieldValue::try_from(serde_json::json!(10)).unwrap();
+    /// ```
+    fn try_from(value: Value) -> std::result::Result<Self, Self::Error> {
+        match value {
+            Value::Number(number) => {
+                if let Some(number) = number.as_i64() {
+                    Ok(FieldValue::Integer(number))
+                } else if let Some(number) = number.as_u64() {
+                    Ok(FieldValue::UInteger(number))
+                } else {
+                    number
+                        .as_f64()
+                        .map(FieldValue::Float)
+                        .ok_or(BuilderError::UnsupportedJsonValue("number"))
+                }
+            }
+            Value::Bool(boolean) => Ok(FieldValue::Boolean(boolean)),
+            Value::String(string) => Ok(FieldValue::String(string)),
+            Value::Null => Err(BuilderError::UnsupportedJsonValue("null")),
+            Value::Array(_) => Err(BuilderError::UnsupportedJsonValue("array")),
+            Value::Object(_) => Err(BuilderError::UnsupportedJsonValue("object")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_scalars() {
+        let values = [
+            (json!(1.5), FieldValue::Float(1.5)),
+            (json!(-10), FieldValue::Integer(-10)),
+            (json!(10), FieldValue::Integer(10)),
+            (json!(u64::MAX), FieldValue::UInteger(u64::MAX)),
+            (json!(true), FieldValue::Boolean(true)),
+            (json!("value"), FieldValue::String("value".into())),
+        ];
+
+        for (value, expected) in values {
+            assert_eq!(FieldValue::try_from(value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_json_unsupported() {
+        let values = [
+            (json!(null), "null"),
+            (json!([1, 2]), "array"),
+            (json!({ "key": 1 }), "object"),
+        ];
+
+        for (value, expected) in values {
+            match FieldValue::try_from(value) {
+                Err(BuilderError::UnsupportedJsonValue(kind)) => assert_eq!(kind, expected),
+                other => panic!("expected unsupported json value, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_add_fields() {
+        let payload = json!({
+            "temperature": 21.5,
+            "status": "ok",
+            "cpu": { "user": 10 },
+            "samples": [1, 2],
+            "missing": null,
+        });
+        let object = payload.as_object().unwrap();
+
+        let line_protocol = LineProtocol::new("measurement").add_json_fields(object);
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement status=\"ok\",temperature=21.5"
+        );
+
+        let options = JsonOptions::new().flatten_nested(true);
+        let line_protocol = LineProtocol::new("measurement")
+            .add_json_fields_with(object, &options)
+            .unwrap();
+        assert_eq!(
+            line_protocol.build().unwrap(),
+            "measurement cpu.user=10i,status=\"ok\",temperature=21.5"
+        );
+
+        let options = JsonOptions::new().flatten_nested(true).strict(true);
+        let result = LineProtocol::new("measurement").add_json_fields_with(object, &options);
+        assert!(result.is_err());
+
+        let options = JsonOptions::new().strict(true);
+        let payload = json!({ "cpu": { "user": 10 } });
+        let result = LineProtocol::new("measurement")
+            .add_json_fields_with(payload.as_object().unwrap(), &options);
+        assert!(result.is_err());
+    }
+}
@@ -103,26 +103,85 @@
 //!
 //! **Note:** The parsed line can be modified and rebuilt if needed
 
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Index,
+};
 
-use element::{FieldKey, FieldValue, Measurement, TagKey, TagValue};
+use element::{AttrKind, Attribute, FieldKey, FieldValue, Measurement, TagKey, TagValue};
+use precision::TimestampPrecision;
+use traits::Format;
 
+pub mod batch;
+pub mod binary;
+pub mod borrowed;
 pub mod builder;
+#[cfg(feature = "chrono")]
+pub mod datetime;
+#[cfg(feature = "serde")]
+pub mod de;
+pub mod diff;
 pub mod element;
 pub mod error;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod parser;
+pub mod point;
+pub mod precision;
+pub mod schema;
+pub mod sink;
+pub mod stream;
 pub mod traits;
 
-#[derive(Debug, Clone)]
+/// The map holding the tag set and field set of a [LineProtocol]
+///
+/// A [HashMap](std::collections::HashMap) by default. With the `indexmap`
+/// feature enabled it is an [IndexMap](indexmap::IndexMap) which keeps the
+/// tags and fields in insertion order, see [LineProtocol::build_preserve_order]
+#[cfg(not(feature = "indexmap"))]
+pub type Map<K, V> = std::collections::HashMap<K, V>;
+
+/// The map holding the tag set and field set of a [LineProtocol]
+///
+/// A [HashMap](std::collections::HashMap) by default. With the `indexmap`
+/// feature enabled it is an [IndexMap](indexmap::IndexMap) which keeps the
+/// tags and fields in insertion order, see [LineProtocol::build_preserve_order]
+#[cfg(feature = "indexmap")]
+pub type Map<K, V> = indexmap::IndexMap<K, V>;
+
+/// Remove a key from a [Map], keeping the order of the remaining entries
+pub(crate) fn map_remove<K, V>(map: &mut Map<K, V>, key: &K) -> Option<V>
+where
+    K: Hash + Eq,
+{
+    #[cfg(feature = "indexmap")]
+    return map.shift_remove(key);
+
+    #[cfg(not(feature = "indexmap"))]
+    return map.remove(key);
+}
+
+/// A single InfluxDB line protocol data point
+///
+/// # Equality
+/// Two data points are only equal if all of their parts are equal, including
+/// the field values. Data points sharing the measurement, tags, and timestamp
+/// but differing in field values are **not** equal, even though they describe
+/// the same series at the same time. Use [LineProtocol::same_series] for that
+/// "series identity" check, e.g., to find data points which would overwrite
+/// each other when written to InfluxDB
+#[derive(Clone)]
 pub struct LineProtocol {
     /// The data point measurement name
     pub measurement: Measurement,
 
     /// The data point tag set
-    pub tags: Option<HashMap<TagKey, TagValue>>,
+    pub tags: Option<Map<TagKey, TagValue>>,
 
     /// The data point field set
-    pub fields: HashMap<FieldKey, FieldValue>,
+    pub fields: Map<FieldKey, FieldValue>,
 
     /// To ensure a data point includes the time a metric is observed (not
     /// received by InfluxDB), include a timestamp if not defined
@@ -134,30 +193,105 @@ pub struct LineProtocol {
     // as the precision is defined when you query the database. But the min/max timestamp value is
     // exactly a i64 https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#unix-timestamp
     pub timestamp: Option<i64>,
+
+    /// The precision of the timestamp. Defaults to nanoseconds, see
+    /// [LineProtocol::build_with_precision] for emitting the timestamp in
+    /// another precision
+    pub precision: TimestampPrecision,
 }
 
+/// Two data points are equal if all of their parts are equal, i.e., the
+/// measurement, tags, fields, timestamp and its precision. A missing tag set is
+/// equal to an empty one
+///
+/// Use [LineProtocol::same_series] to check if two data points only share the
+/// same series and timestamp
 impl PartialEq for LineProtocol {
     fn eq(&self, other: &Self) -> bool {
-        if self.measurement != other.measurement {
-            println!("name not equal");
-            return false;
+        self.same_series(other) && self.fields == other.fields && self.precision == other.precision
+    }
+}
+
+impl Eq for LineProtocol {}
+
+/// Consistent with [PartialEq], meaning equal data points have equal hashes
+impl Hash for LineProtocol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.measurement.hash(state);
+
+        let tags = self.tags.iter().flatten().collect::<BTreeMap<_, _>>();
+        tags.len().hash(state);
+        for (key, value) in tags {
+            key.hash(state);
+            value.0.hash(state);
         }
 
-        let tags_matches = match (&self.tags, &other.tags) {
-            (Some(tags1), Some(tags2)) => tags1 == tags2,
-            (None, None) => true,
-            _ => return false,
-        };
+        let fields = self.fields.iter().collect::<BTreeMap<_, _>>();
+        fields.len().hash(state);
+        for (key, value) in fields {
+            key.hash(state);
+            value.hash(state);
+        }
 
-        let timestamp_matches = match (self.timestamp, other.timestamp) {
-            (Some(ts1), Some(ts2)) => ts1 == ts2,
-            (None, None) => true,
-            _ => return false,
-        };
+        self.timestamp.hash(state);
+        self.precision.hash(state);
+    }
+}
+
+/// Renders the tags and fields sorted by key, making the output deterministic,
+/// e.g., for snapshot tests
+impl std::fmt::Debug for LineProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tags = self
+            .tags
+            .as_ref()
+            .map(|tags| tags.iter().collect::<BTreeMap<_, _>>());
+        let fields = self.fields.iter().collect::<BTreeMap<_, _>>();
+
+        f.debug_struct("LineProtocol")
+            .field("measurement", &self.measurement)
+            .field("tags", &tags)
+            .field("fields", &fields)
+            .field("timestamp", &self.timestamp)
+            .field("precision", &self.precision)
+            .finish()
+    }
+}
+
+/// Read a field value by key, e.g., `line_protocol["temperature"]`
+///
+/// # Panics
+/// Panics if the field does not exist. Use [LineProtocol::get_field_ref] if the
+/// field may be absent
+impl Index<&str> for LineProtocol {
+    type Output = FieldValue;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        self.fields
+            .get(&FieldKey::from(key))
+            .unwrap_or_else(|| panic!("field '{key}' does not exist"))
+    }
+}
+
+/// A read-only view of the tag set of a [LineProtocol] which supports
+/// indexing by tag key, see [LineProtocol::tag_view]
+#[derive(Debug, Clone, Copy)]
+pub struct TagView<'a> {
+    tags: Option<&'a Map<TagKey, TagValue>>,
+}
 
-        // At this point we know the measurement is equal. If the tags and timestamp are
-        // also equal its a duplicate line
-        tags_matches && timestamp_matches
+/// Read a tag value by key, e.g., `line_protocol.tag_view()["host"]`
+///
+/// # Panics
+/// Panics if the tag does not exist. Use [LineProtocol::get_tag_ref] if the tag
+/// may be absent
+impl Index<&str> for TagView<'_> {
+    type Output = TagValue;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        self.tags
+            .and_then(|tags| tags.get(&TagKey::from(key)))
+            .unwrap_or_else(|| panic!("tag '{key}' does not exist"))
     }
 }
 
@@ -234,6 +368,44 @@ impl LineProtocol {
         }
     }
 
+    /// Check if the data point has a tag with the provided tag key
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   tag key
+    pub fn contains_tag<K>(&self, key: K) -> bool
+    where
+        K: Into<TagKey>,
+    {
+        match &self.tags {
+            Some(tags) => tags.contains_key(&key.into()),
+            None => false,
+        }
+    }
+
+    /// Get a view of the tag set which can be indexed by tag key
+    ///
+    /// Field values can be indexed directly on the data point, tags go through
+    /// the view to avoid ambiguity between a tag and a field with the same key
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "server01")
+    ///     .add_field("temperature", 21.5);
+    ///
+    /// let host = &line_protocol.tag_view()["host"];
+    /// let temperature = &line_protocol["temperature"];
+    /// ```
+    ///
+    /// # Panics
+    /// Indexing the view panics if the tag does not exist
+    pub fn tag_view(&self) -> TagView<'_> {
+        TagView {
+            tags: self.tags.as_ref(),
+        }
+    }
+
     /// Get the field value associated with the provided field key
     ///
     /// # Args
@@ -272,6 +444,18 @@ impl LineProtocol {
         self.fields.get_mut(&key.into())
     }
 
+    /// Check if the data point has a field with the provided field key
+    ///
+    /// # Args
+    /// * `key` - A [valid](https://docs.influxdata.com/influxdb/cloud/reference/syntax/line-protocol/#special-characters)
+    ///   field key
+    pub fn contains_field<K>(&self, key: K) -> bool
+    where
+        K: Into<FieldKey>,
+    {
+        self.fields.contains_key(&key.into())
+    }
+
     /// Get a cloned version of the timestamp
     pub fn get_timestamp(&self) -> Option<i64> {
         self.timestamp
@@ -286,4 +470,641 @@ impl LineProtocol {
     pub fn get_timestamp_mut(&mut self) -> Option<&mut i64> {
         self.timestamp.as_mut()
     }
+
+    /// Check if the data point has a timestamp
+    pub fn has_timestamp(&self) -> bool {
+        self.timestamp.is_some()
+    }
+
+    /// The timestamp in nanoseconds, widened so that no precision overflows
+    pub(crate) fn timestamp_nanos(&self) -> Option<i128> {
+        self.timestamp.map(|timestamp| {
+            i128::from(timestamp) * i128::from(self.precision.nanoseconds_per_unit())
+        })
+    }
+
+    /// Check if two data points belong to the same series at the same time,
+    /// i.e., they share the measurement, tags, and timestamp
+    ///
+    /// Unlike equality the fields are ignored, meaning the data points would
+    /// overwrite each other when written to InfluxDB. A missing tag set is
+    /// equal to an empty one, and timestamps are compared in nanoseconds so
+    /// that `1` second is the same time as `1_000_000_000` nanoseconds
+    ///
+    /// # Example
+    /// ```rust
+    /// let a = LineProtocol::new("measurement").add_field("field", 1);
+    /// let b = LineProtocol::new("measurement").add_field("field", 2);
+    ///
+    /// assert!(a.same_series(&b));
+    /// assert_ne!(a, b);
+    /// ```
+    ///
+    /// # Args
+    /// * `other` - The data point to compare with
+    pub fn same_series(&self, other: &LineProtocol) -> bool {
+        let tags_match = match (&self.tags, &other.tags) {
+            (Some(tags1), Some(tags2)) => tags1 == tags2,
+            (Some(tags), None) | (None, Some(tags)) => tags.is_empty(),
+            (None, None) => true,
+        };
+
+        self.measurement == other.measurement
+            && tags_match
+            && self.timestamp_nanos() == other.timestamp_nanos()
+    }
+
+    /// Iterate over the tag set in arbitrary order
+    ///
+    /// Yields nothing if the data point has no tags
+    pub fn tags_iter(&self) -> impl Iterator<Item = (&TagKey, &TagValue)> {
+        self.tags.iter().flatten()
+    }
+
+    /// Iterate over the field set in arbitrary order
+    pub fn fields_iter(&self) -> impl Iterator<Item = (&FieldKey, &FieldValue)> {
+        self.fields.iter()
+    }
+
+    /// Get the amount of tags
+    pub fn tag_count(&self) -> usize {
+        self.tags.as_ref().map_or(0, |tags| tags.len())
+    }
+
+    /// Get the amount of fields
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Get the tag keys sorted by key
+    ///
+    /// Empty if the data point has no tags
+    pub fn tag_keys(&self) -> Vec<&TagKey> {
+        let mut keys = self.tags_iter().map(|(key, _)| key).collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Get the field keys sorted by key
+    pub fn field_keys(&self) -> Vec<&FieldKey> {
+        let mut keys = self.fields.keys().collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Get a copy of the tag set sorted by tag key
+    ///
+    /// The map is empty if the data point has no tags
+    pub fn tags_btreemap(&self) -> BTreeMap<TagKey, TagValue> {
+        match &self.tags {
+            Some(tags) => tags.clone().into_iter().collect(),
+            None => BTreeMap::new(),
+        }
+    }
+
+    /// Get a copy of the field set sorted by field key
+    pub fn fields_btreemap(&self) -> BTreeMap<FieldKey, FieldValue> {
+        self.fields.clone().into_iter().collect()
+    }
+
+    /// Get the series key of the data point
+    ///
+    /// A series is identified by the measurement and tag set. The key is the
+    /// escaped measurement followed by the tags sorted by key, i.e., the part
+    /// of the line protocol string before the field set
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("tag2", "value")
+    ///     .add_tag("tag1", "value")
+    ///     .add_field("field", "value");
+    ///
+    /// let key = line_protocol.series_key();
+    /// // Output: measurement,tag1=value,tag2=value
+    /// ```
+    pub fn series_key(&self) -> String {
+        let mut key = self.measurement.escape_cow().into_owned();
+        for (tag_key, tag_value) in self.tags_btreemap() {
+            key = format!("{key},{}={}", tag_key.escape_cow(), tag_value.escape_cow());
+        }
+
+        key
+    }
+
+    /// Find pairs of tags whose escaped `key=value` forms collide
+    ///
+    /// Escaping itself never maps two distinct tags to the same string. A
+    /// collision happens when tags are added with mixed escaping, e.g., one
+    /// tag key is added as `host name` and another is added already escaped
+    /// as `host\ name`. Both are meant to be the same tag but end up as two
+    /// tags in the same series. To detect this each tag is compared after
+    /// unescaping and escaping it again
+    ///
+    /// The pairs are sorted by tag key and the first key of each pair is the
+    /// lowest
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host name", "a")
+    ///     .add_tag("host\\ name", "a")
+    ///     .add_field("field", "value");
+    ///
+    /// let collisions = line_protocol.detect_escape_collisions();
+    /// // Output: [(TagKey("host name"), TagKey("host\\ name"))]
+    /// ```
+    pub fn detect_escape_collisions(&self) -> Vec<(TagKey, TagKey)> {
+        let normalized = self
+            .tags_btreemap()
+            .into_iter()
+            .map(|(key, value)| {
+                let escaped = format!("{}={}", key.unescape().escape(), value.unescape().escape());
+                (key, escaped)
+            })
+            .collect::<Vec<_>>();
+
+        let mut collisions = Vec::new();
+        for (index, (key, escaped)) in normalized.iter().enumerate() {
+            for (other_key, other_escaped) in &normalized[index + 1..] {
+                if escaped == other_escaped {
+                    collisions.push((key.clone(), other_key.clone()));
+                }
+            }
+        }
+
+        collisions
+    }
+
+    /// Get a copy of the data point only containing the provided fields
+    ///
+    /// The measurement, tags, and timestamp are kept as is. Keys which do not
+    /// exist in the data point are ignored, meaning if none of the keys exist
+    /// the returned data point has no fields and will fail to build
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_field("field1", "value")
+    ///     .add_field("field2", "value")
+    ///     .add_field("field3", "value");
+    ///
+    /// let projected = line_protocol.project_fields(["field1", "field3"]);
+    /// ```
+    ///
+    /// # Args
+    /// * `keys` - The field keys to keep
+    pub fn project_fields<I, K>(&self, keys: I) -> LineProtocol
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<FieldKey>,
+    {
+        let fields = keys
+            .into_iter()
+            .filter_map(|key| {
+                let key = key.into();
+                self.fields.get(&key).cloned().map(|value| (key, value))
+            })
+            .collect();
+
+        LineProtocol {
+            measurement: self.measurement.clone(),
+            tags: self.tags.clone(),
+            fields,
+            timestamp: self.timestamp,
+            precision: self.precision,
+        }
+    }
+
+    /// Get a copy of the data point where every string is an owned [String]
+    /// independent of any input buffer
+    ///
+    /// All elements of a [LineProtocol] are owned, making this equivalent to
+    /// [Clone::clone]. Use this over `clone` when caching a data point long
+    /// term to keep the guarantee should borrowed elements be introduced
+    ///
+    /// # Example
+    /// ```rust
+    /// let parsed_line = LineProtocol::parse_line(&line).unwrap();
+    /// drop(line);
+    ///
+    /// cache.insert(parsed_line.series_key(), parsed_line.to_owned_deep());
+    /// ```
+    pub fn to_owned_deep(&self) -> LineProtocol {
+        self.clone()
+    }
+
+    /// Iterate over all tags, fields, and the timestamp as [Attribute]s
+    ///
+    /// The tags come first sorted by key, then the fields sorted by key, and
+    /// lastly the timestamp if it is defined
+    ///
+    /// # Example
+    /// ```rust
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("tag", "value")
+    ///     .add_field("field", 10)
+    ///     .with_timestamp(1729270461612452700i64);
+    ///
+    /// for attribute in line_protocol.attributes() {
+    ///     println!("{:?} {}={}", attribute.kind, attribute.key, attribute.value);
+    /// }
+    /// // Output:
+    /// // Tag tag=value
+    /// // Field field=10i
+    /// // Timestamp timestamp=1729270461612452700
+    /// ```
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> {
+        let tags = self
+            .tags_btreemap()
+            .into_iter()
+            .map(|(key, value)| Attribute {
+                kind: AttrKind::Tag,
+                key: key.0,
+                value: value.0,
+            });
+
+        let fields = self
+            .fields_btreemap()
+            .into_iter()
+            .map(|(key, value)| Attribute {
+                kind: AttrKind::Field,
+                key: key.0,
+                value: value.to_string(),
+            });
+
+        let timestamp = self.timestamp.map(|timestamp| Attribute {
+            kind: AttrKind::Timestamp,
+            key: "timestamp".to_string(),
+            value: timestamp.to_string(),
+        });
+
+        tags.chain(fields).chain(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_btreemap_sorted() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("b", "value")
+            .add_tag("c", "value")
+            .add_tag("a", "value")
+            .add_field("field3", 3)
+            .add_field("field1", 1)
+            .add_field("field2", 2);
+
+        let tags = line_protocol.tags_btreemap();
+        let tag_keys = tags.keys().map(|key| key.0.as_str()).collect::<Vec<_>>();
+        assert_eq!(tag_keys, vec!["a", "b", "c"]);
+
+        let fields = line_protocol.fields_btreemap();
+        let field_keys = fields.keys().map(|key| key.0.as_str()).collect::<Vec<_>>();
+        assert_eq!(field_keys, vec!["field1", "field2", "field3"]);
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        assert!(line_protocol.tags_btreemap().is_empty());
+    }
+
+    #[test]
+    fn test_series_key() {
+        let line_protocol = LineProtocol::new("my measurement")
+            .add_tag("tag2", "value")
+            .add_tag("tag1", "some value")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64);
+        assert_eq!(
+            line_protocol.series_key(),
+            "my\\ measurement,tag1=some\\ value,tag2=value"
+        );
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+        assert_eq!(line_protocol.series_key(), "measurement");
+    }
+
+    #[test]
+    fn test_project_fields() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field1", 1)
+            .add_field("field2", 2)
+            .add_field("field3", 3)
+            .add_field("field4", 4)
+            .with_timestamp(1729270461612452700i64);
+
+        let projected = line_protocol.project_fields(["field1", "field3", "missing"]);
+        assert_eq!(
+            projected.build().unwrap(),
+            "measurement,tag=value field1=1i,field3=3i 1729270461612452700"
+        );
+
+        // The original data point is left untouched
+        assert_eq!(line_protocol.fields.len(), 4);
+    }
+
+    #[test]
+    fn test_project_fields_none_existing_is_err() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+
+        let projected = line_protocol.project_fields(["missing"]);
+        assert!(projected.fields.is_empty());
+        assert!(projected.build().is_err());
+    }
+
+    #[test]
+    fn test_attributes() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag2", "value2")
+            .add_tag("tag1", "value1")
+            .add_field("field2", 10)
+            .add_field("field1", "hello world")
+            .with_timestamp(1729270461612452700i64);
+
+        let attributes = line_protocol
+            .attributes()
+            .map(|attr| (attr.kind, attr.key, attr.value))
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            (AttrKind::Tag, "tag1".to_string(), "value1".to_string()),
+            (AttrKind::Tag, "tag2".to_string(), "value2".to_string()),
+            (
+                AttrKind::Field,
+                "field1".to_string(),
+                "hello world".to_string(),
+            ),
+            (AttrKind::Field, "field2".to_string(), "10i".to_string()),
+            (
+                AttrKind::Timestamp,
+                "timestamp".to_string(),
+                "1729270461612452700".to_string(),
+            ),
+        ];
+        assert_eq!(attributes, expected);
+
+        // Without tags and timestamp only the fields are included
+        let line_protocol = LineProtocol::new("measurement").add_field("field", true);
+        let attributes = line_protocol.attributes().collect::<Vec<_>>();
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0].kind, AttrKind::Field);
+    }
+
+    #[test]
+    fn test_to_owned_deep() {
+        let line = String::from("measurement,tag=value field=\"value\" 1729270461612452700");
+        let parsed_line = LineProtocol::parse_line(&line).unwrap();
+        drop(line);
+
+        let mut owned = parsed_line.to_owned_deep();
+        assert_eq!(owned.build().unwrap(), parsed_line.build().unwrap());
+        assert_ne!(
+            owned.measurement.0.as_ptr(),
+            parsed_line.measurement.0.as_ptr()
+        );
+
+        // Modifying the copy leaves the original untouched
+        owned.add_field_ref("field", "changed");
+        assert_eq!(
+            parsed_line.get_field("field"),
+            Some(FieldValue::String("value".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_iterators_and_counts() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag1", "value")
+            .add_tag("tag2", "value")
+            .add_field("field1", 1.5)
+            .add_field("field2", 2.5)
+            .add_field("field3", 3.0);
+
+        assert_eq!(line_protocol.tag_count(), 2);
+        assert_eq!(line_protocol.tags_iter().count(), 2);
+        assert_eq!(line_protocol.field_count(), 3);
+
+        let sum = line_protocol
+            .fields_iter()
+            .filter_map(|(_, value)| match value {
+                FieldValue::Float(number) => Some(number),
+                _ => None,
+            })
+            .sum::<f64>();
+        assert_eq!(sum, 7.0);
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        assert_eq!(line_protocol.tag_count(), 0);
+        assert_eq!(line_protocol.tags_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_detect_escape_collisions() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("host name", "a")
+            .add_tag(r"host\ name", "a")
+            .add_tag("region", "eu west")
+            .add_tag("region2", "eu west")
+            .add_field("field", "value");
+
+        let collisions = line_protocol.detect_escape_collisions();
+        assert_eq!(
+            collisions,
+            vec![(TagKey::from("host name"), TagKey::from(r"host\ name"))]
+        );
+
+        // The escaped forms differ as the tag values differ
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("host name", "a")
+            .add_tag(r"host\ name", "b")
+            .add_field("field", "value");
+        assert!(line_protocol.detect_escape_collisions().is_empty());
+    }
+
+    #[test]
+    fn test_lib_contains() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+        assert!(!line_protocol.contains_tag("tag"));
+        assert!(line_protocol.contains_field("field"));
+        assert!(!line_protocol.contains_field("other"));
+        assert!(!line_protocol.has_timestamp());
+
+        let line_protocol = line_protocol
+            .add_tag("tag", "value")
+            .with_timestamp(1729270461612452700i64);
+        assert!(line_protocol.contains_tag("tag"));
+        assert!(!line_protocol.contains_tag("other"));
+        assert!(line_protocol.has_timestamp());
+    }
+
+    #[test]
+    fn test_lib_same_series() {
+        let a = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1)
+            .with_timestamp(1i64);
+        let b = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 2)
+            .with_timestamp(1i64);
+        assert!(a.same_series(&b));
+        assert_ne!(a, b);
+
+        assert!(!a.same_series(&b.clone().with_timestamp(2i64)));
+        assert!(!a.same_series(&b.clone().add_tag("other", "value")));
+        assert!(!a.same_series(&b.clone().measurement("other")));
+
+        // A missing tag set is equal to an empty one
+        let mut c = LineProtocol::new("measurement").add_field("field", 1);
+        let d = c.clone();
+        c.tags = Some(Map::new());
+        assert!(c.same_series(&d));
+        assert_eq!(c, d);
+    }
+
+    #[test]
+    fn test_lib_same_series_mixed_precision() {
+        let a = LineProtocol::new("measurement")
+            .add_field("field", 1)
+            .with_timestamp(1i64);
+        let b = a
+            .clone()
+            .with_timestamp_precision(1i64, TimestampPrecision::Seconds);
+        assert!(!a.same_series(&b));
+
+        let c = a
+            .clone()
+            .with_timestamp_precision(1_000_000_000i64, TimestampPrecision::Nanoseconds);
+        assert!(b.same_series(&c));
+        assert!(b.same_series(
+            &a.clone()
+                .with_timestamp_precision(1_000i64, TimestampPrecision::Milliseconds)
+        ));
+    }
+
+    #[test]
+    fn test_lib_hash_dedup() {
+        use std::collections::HashSet;
+
+        let lines = [
+            "measurement,a=1,b=2 x=1i,y=\"value\" 1",
+            "measurement,b=2,a=1 y=\"value\",x=1i 1",
+            "measurement,a=1,b=2 x=2i,y=\"value\" 1",
+            "measurement,a=1,b=2 x=1i,y=\"value\" 2",
+        ];
+
+        let points = lines
+            .iter()
+            .map(|line| LineProtocol::parse_line(line).unwrap())
+            .collect::<HashSet<_>>();
+        assert_eq!(points.len(), 3);
+
+        // Field values of different types are never equal, even if they are
+        // written the same way
+        let pairs = [
+            (FieldValue::String("true".into()), FieldValue::Boolean(true)),
+            (FieldValue::String("1".into()), FieldValue::Float(1.0)),
+            (FieldValue::String("10i".into()), FieldValue::Integer(10)),
+            (FieldValue::Integer(10), FieldValue::UInteger(10)),
+        ];
+
+        for (a, b) in pairs {
+            let a = LineProtocol::new("measurement").add_field("field", a);
+            let b = LineProtocol::new("measurement").add_field("field", b);
+            assert_ne!(a, b);
+
+            let points = [a, b].into_iter().collect::<HashSet<_>>();
+            assert_eq!(points.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_lib_eq_compares_fields() {
+        let a = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", 1)
+            .with_timestamp(1i64);
+
+        assert_eq!(a, a.clone());
+        assert_ne!(a, a.clone().add_field("field", 2));
+        assert_ne!(a, a.clone().add_field("other", 1));
+        assert_ne!(a, a.clone().measurement("other"));
+        assert_ne!(
+            a,
+            a.clone()
+                .with_timestamp_precision(1i64, TimestampPrecision::Seconds)
+        );
+    }
+
+    #[test]
+    fn test_lib_keys() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("zone", "a")
+            .add_tag("host", "b")
+            .add_field("value", 0.5)
+            .add_field("count", 1)
+            .add_field("status", "ok");
+
+        assert_eq!(
+            line_protocol.tag_keys(),
+            vec![&TagKey::from("host"), &TagKey::from("zone")]
+        );
+        assert_eq!(
+            line_protocol.field_keys(),
+            vec![
+                &FieldKey::from("count"),
+                &FieldKey::from("status"),
+                &FieldKey::from("value")
+            ]
+        );
+
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        assert!(line_protocol.tag_keys().is_empty());
+    }
+
+    #[test]
+    fn test_lib_index() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("host", "server01")
+            .add_field("host", 1)
+            .add_field("temperature", 21.5);
+
+        assert_eq!(line_protocol["temperature"], FieldValue::Float(21.5));
+        assert_eq!(line_protocol["host"], FieldValue::Integer(1));
+        assert_eq!(line_protocol.tag_view()["host"], TagValue::from("server01"));
+    }
+
+    #[test]
+    #[should_panic(expected = "field 'missing' does not exist")]
+    fn test_lib_index_missing_field() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        let _ = &line_protocol["missing"];
+    }
+
+    #[test]
+    #[should_panic(expected = "tag 'missing' does not exist")]
+    fn test_lib_index_missing_tag() {
+        let line_protocol = LineProtocol::new("measurement").add_field("field", 1);
+        let _ = &line_protocol.tag_view()["missing"];
+    }
+
+    #[test]
+    fn test_lib_debug_sorted() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("b", "2")
+            .add_tag("a", "1")
+            .add_field("z", 10.0)
+            .add_field("y", 1)
+            .add_field("x", "value");
+
+        assert_eq!(
+            format!("{line_protocol:?}"),
+            "LineProtocol { measurement: Measurement(\"measurement\"), tags: Some({TagKey(\"a\"): \
+             TagValue(\"1\"), TagKey(\"b\"): TagValue(\"2\")}), fields: {FieldKey(\"x\"): \
+             String(\"value\"), FieldKey(\"y\"): Integer(1), FieldKey(\"z\"): Float(10.0)}, \
+             timestamp: None, precision: Nanoseconds }"
+        );
+    }
 }
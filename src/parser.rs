@@ -7,20 +7,235 @@
 //! 3. [LineProtocol::parse_vec]
 //!     - Parse multiple lines stored in a vector into a vector of
 //!       [LineProtocol] structs
+//!
+//! The methods follow the line protocol specification strictly. To tolerate
+//! input from producers which do not, use [LineProtocol::parse_line_with] with
+//! a set of [ParseOptions]
 
-use std::{collections::HashMap, hash::Hash};
+use std::{fmt::Display, hash::Hash, io::BufRead, sync::Arc};
 
-use crate::error::{ParseError, Result};
+use crate::error::{BoxError, LineProtocolError, ParseError, Result};
 
 use crate::{
     element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
+    map_remove,
+    precision::TimestampPrecision,
     traits::{Convert, Format},
-    LineProtocol,
+    LineProtocol, Map,
 };
 
+/// UTF-8 byte order mark
+const BOM: char = '\u{FEFF}';
+
+/// What to do with a field named `time` when parsing a line
+///
+/// InfluxDB treats a field named `time` specially, it can't be queried as a
+/// regular field as it collides with the timestamp column
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TimeFieldPolicy {
+    /// Keep the field as is
+    #[default]
+    Keep,
+
+    /// Fail to parse the line with [ParseError::TimeField]
+    Reject,
+
+    /// Rename the field to the provided field key. An existing field with the
+    /// same key is overwritten
+    RenameTo(String),
+}
+
+/// What to do with duplicate data points when parsing multiple lines, see
+/// [LineProtocol::parse_vec_with]
+///
+/// Two data points are duplicates if they share the same series and timestamp,
+/// i.e., the measurement, tag set, and timestamp are equal, see
+/// [LineProtocol::same_series]. The fields are not compared
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DedupPolicy {
+    /// Merge the fields of a duplicate into the first data point, a field
+    /// defined by both keeps the value of the latter
+    #[default]
+    MergeFields,
+
+    /// Keep every data point as is
+    KeepAll,
+
+    /// Fail with [ParseError::DuplicatePoint]
+    Error,
+}
+
+/// A line of a line protocol file, see [LineProtocol::parse_vec_preserving]
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineEntry {
+    /// A data point
+    Point(LineProtocol),
+
+    /// A comment line with the text following the leading `#`
+    Comment(String),
+
+    /// An empty line or a line only containing whitespace
+    Blank,
+}
+
+/// A parser for raw set values returning `None` if the value is not recognized
+type ValueParser<V> = dyn Fn(&str) -> Option<V> + Send + Sync;
+
+/// A parser for raw field values, see [ParseOptions::custom_field_parser]
+pub type CustomFieldParser = Arc<ValueParser<FieldValue>>;
+
+/// Options used to change how a line is parsed
+///
+/// By default the options follow the line protocol specification strictly.
+/// The options are meant to tolerate input from producers which do not
+///
+/// # Example
+/// ```rust
+/// let options = ParseOptions::new().strip_tag_quotes(true);
+/// let parsed_line = LineProtocol::parse_line_with(line, &options).unwrap();
+/// ```
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    default_measurement: Option<Measurement>,
+    strip_tag_quotes: bool,
+    whitespace_delimiters: bool,
+    time_field_policy: TimeFieldPolicy,
+    custom_field_parser: Option<CustomFieldParser>,
+    strict_field_values: bool,
+}
+
+impl std::fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("default_measurement", &self.default_measurement)
+            .field("strip_tag_quotes", &self.strip_tag_quotes)
+            .field("whitespace_delimiters", &self.whitespace_delimiters)
+            .field("time_field_policy", &self.time_field_policy)
+            .field(
+                "custom_field_parser",
+                &self.custom_field_parser.as_ref().map(|_| "Fn"),
+            )
+            .field("strict_field_values", &self.strict_field_values)
+            .finish()
+    }
+}
+
+impl ParseOptions {
+    /// Create a new set of parse options with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the measurement name used if a line does not include one
+    ///
+    /// Disabled by default, meaning a line without a measurement name fails to
+    /// parse
+    ///
+    /// # Args
+    /// * `measurement` - The default measurement name
+    pub fn default_measurement<T>(mut self, measurement: T) -> Self
+    where
+        T: Into<Measurement>,
+    {
+        self.default_measurement = Some(measurement.into());
+        self
+    }
+
+    /// Strip surrounding double quotes from tag values, e.g., `host="a"` is
+    /// parsed as `host=a`
+    ///
+    /// Tag values are never quoted in line protocol so by default the quotes
+    /// are kept as part of the value
+    ///
+    /// # Args
+    /// * `strip` - Whether to strip the quotes
+    pub fn strip_tag_quotes(mut self, strip: bool) -> Self {
+        self.strip_tag_quotes = strip;
+        self
+    }
+
+    /// Treat any ASCII whitespace, e.g., a tab, as a delimiter between the
+    /// parts of a line
+    ///
+    /// Line protocol only recognizes a space as a delimiter so by default any
+    /// other whitespace is treated as part of the measurement, tag set, or
+    /// field set
+    ///
+    /// # Args
+    /// * `enabled` - Whether any ASCII whitespace is a delimiter
+    pub fn whitespace_delimiters(mut self, enabled: bool) -> Self {
+        self.whitespace_delimiters = enabled;
+        self
+    }
+
+    /// Set what happens when a line contains a field named `time`
+    ///
+    /// The field is kept by default, see [TimeFieldPolicy]
+    ///
+    /// # Example
+    /// ```rust
+    /// let options =
+    ///     ParseOptions::new().handle_time_field(TimeFieldPolicy::RenameTo("time_field".to_string()));
+    /// ```
+    ///
+    /// # Args
+    /// * `policy` - The policy applied to a field named `time`
+    pub fn handle_time_field(mut self, policy: TimeFieldPolicy) -> Self {
+        self.time_field_policy = policy;
+        self
+    }
+
+    /// Set a parser consulted for each field value before the default type
+    /// guessing
+    ///
+    /// The parser receives the raw field value as written in the line, e.g.,
+    /// a string field value includes the surrounding double quotes. If the
+    /// parser returns `None` the field value is parsed as usual, otherwise the
+    /// returned field value is used as is
+    ///
+    /// # Example
+    /// ```rust
+    /// let options = ParseOptions::new().custom_field_parser(|value| {
+    ///     let hex = value.strip_prefix("0x")?;
+    ///     u64::from_str_radix(hex, 16).ok().map(FieldValue::UInteger)
+    /// });
+    /// ```
+    ///
+    /// # Args
+    /// * `parser` - The custom field value parser
+    pub fn custom_field_parser<F>(mut self, parser: F) -> Self
+    where
+        F: Fn(&str) -> Option<FieldValue> + Send + Sync + 'static,
+    {
+        self.custom_field_parser = Some(Arc::new(parser));
+        self
+    }
+
+    /// Only accept field values as written in line protocol, see
+    /// [Convert::parse_from_strict](crate::traits::Convert::parse_from_strict)
+    ///
+    /// By default an unquoted value which is not a number or boolean, e.g.,
+    /// `value`, is leniently parsed as a string and `NaN` or `inf` as a float.
+    /// In strict mode these fail to parse. Booleans are the tokens listed by
+    /// the line protocol specification, e.g., `t` or `TRUE`, in both modes and
+    /// quoted string field values are strings in both modes
+    ///
+    /// # Args
+    /// * `enabled` - Whether field values are parsed strictly
+    pub fn strict_field_values(mut self, enabled: bool) -> Self {
+        self.strict_field_values = enabled;
+        self
+    }
+
+    /// Check whether the character delimits two parts of a line
+    fn is_delimiter(&self, char: char) -> bool {
+        char == ' ' || (self.whitespace_delimiters && char.is_ascii_whitespace())
+    }
+}
+
 impl LineProtocol {
     /// Split a line protocol part from the rest of the line protocol
-    fn parse_part<P>(chars: &mut P) -> String
+    fn parse_part<P>(chars: &mut P, options: &ParseOptions) -> String
     where
         P: Iterator<Item = char>,
     {
@@ -29,10 +244,10 @@ impl LineProtocol {
 
         // Parse the measurement name
         let mut part = String::new();
-        while let Some(char) = chars.next() {
+        for char in chars.by_ref() {
             // If the current character is a \ (slash) then we know the next character must
-            // be escaped
-            if char == '\\' {
+            // be escaped, unless the slash itself is escaped
+            if char == '\\' && !is_escaped {
                 is_escaped = true;
             }
             // Toggle the `in_quote` flag if the current character is a double quote and the
@@ -41,7 +256,7 @@ impl LineProtocol {
                 in_quote = !in_quote;
             // If the current character is a ' ' (space) and we are not in a
             // quote or its not escaped we've finished a part
-            } else if char == ' ' && (!is_escaped && !in_quote) {
+            } else if options.is_delimiter(char) && (!is_escaped && !in_quote) {
                 break;
             } else {
                 // We've gone past the escaped character
@@ -56,9 +271,17 @@ impl LineProtocol {
 
     /// Parses a set (tag- or field set) into a hashmap of the defined key-value
     /// types
-    fn parse_set<K, V>(set: &str) -> Result<HashMap<K, V>>
+    ///
+    /// A value accepted by the custom parser is used as is, otherwise it is
+    /// parsed and unescaped as usual, strictly if `strict` is set
+    fn parse_set<K, V>(
+        set: &str,
+        custom_parser: Option<&ValueParser<V>>,
+        strict: bool,
+        mut order: Option<&mut Vec<K>>,
+    ) -> Result<Map<K, V>>
     where
-        K: Format + Convert + Hash + PartialEq + Eq,
+        K: Format + Convert + Hash + PartialEq + Eq + Display + Clone,
         V: Format + Convert,
     {
         let mut in_quote = false;
@@ -68,8 +291,8 @@ impl LineProtocol {
         let mut words = Vec::new();
         for char in set.chars() {
             // If the current character is a \ (slash) then we know the next character must
-            // be escaped
-            if char == '\\' {
+            // be escaped, unless the slash itself is escaped
+            if char == '\\' && !is_escaped {
                 is_escaped = true;
                 word.push(char);
             }
@@ -79,10 +302,9 @@ impl LineProtocol {
                 in_quote = !in_quote;
                 word.push(char);
             }
-            // If the current character is a `=` (equals sign) and its not escaped we've finished a
-            // word or if the current character is a `,` (comma) and we are not in a quote we've
-            // finished a word
-            else if (char == '=' && !is_escaped) || (char == ',' && !in_quote) {
+            // If the current character is a `=` (equals sign) or a `,` (comma) which is not
+            // escaped and we are not in a quote we've finished a word
+            else if (char == '=' || char == ',') && !is_escaped && !in_quote {
                 words.push(word.clone());
                 word.clear();
                 continue;
@@ -101,21 +323,41 @@ impl LineProtocol {
         }
         words.push(word);
 
-        // If we don't have an even number of words the given set is invalid
-        if words.len() % 2 != 0 {
+        // If we don't have an even number of words or a key or value is empty, e.g.,
+        // `tag=,other=value`, the given set is invalid
+        if words.len() % 2 != 0 || words.iter().any(|word| word.is_empty()) {
             return Err(
                 ParseError::InvalidSet("set contains uneven amount of values".into()).into(),
             );
         }
 
         // Transform to a hashmap and unescape words
-        let mut set = HashMap::new();
+        let mut set = Map::new();
         for word in words.chunks_exact(2) {
             // Only FieldValue can actually return an error
             let key = K::parse_from(&word[0]).map_err(|e| ParseError::InvalidSet(e.into()))?;
-            let value = V::parse_from(&word[1]).map_err(|e| ParseError::InvalidSet(e.into()))?;
+            let value = match custom_parser.and_then(|parser| parser(&word[1])) {
+                Some(value) => value,
+                None if strict => V::parse_from_strict(&word[1])
+                    .map_err(ParseError::from_value_error)?
+                    .unescape(),
+                None => V::parse_from(&word[1])
+                    .map_err(ParseError::from_value_error)?
+                    .unescape(),
+            };
+
+            // Duplicate keys are rejected by InfluxDB, don't silently overwrite the value
+            let key = key.unescape();
+            if set.contains_key(&key) {
+                return Err(ParseError::DuplicateKey(key.to_string()).into());
+            }
+
+            // Record the order the keys appear in if requested
+            if let Some(order) = order.as_deref_mut() {
+                order.push(key.clone());
+            }
 
-            set.insert(key.unescape(), value.unescape());
+            set.insert(key, value);
         }
 
         Ok(set)
@@ -124,15 +366,16 @@ impl LineProtocol {
     /// Parses the identifier (measurement and tag set)
     fn parse_identifiers(
         input: String,
-    ) -> Result<(Measurement, Option<HashMap<TagKey, TagValue>>)> {
+        options: &ParseOptions,
+    ) -> Result<(Measurement, Option<Map<TagKey, TagValue>>)> {
         let mut chars = input.chars();
         let mut is_escaped = false;
 
         let mut measurement = String::new();
-        while let Some(char) = chars.next() {
+        for char in chars.by_ref() {
             // If the current character is a \ (slash) then we know the next character must
-            // be escaped
-            if char == '\\' {
+            // be escaped, unless the slash itself is escaped
+            if char == '\\' && !is_escaped {
                 is_escaped = true;
             } else if char == ',' && !is_escaped {
                 break;
@@ -143,17 +386,32 @@ impl LineProtocol {
             measurement.push(char);
         }
 
-        if measurement.is_empty() {
-            return Err(ParseError::MissingMeasurement.into());
-        }
-        let measurement = Measurement::from(measurement).unescape();
+        let measurement = match (measurement.is_empty(), &options.default_measurement) {
+            (false, _) => Measurement::from(measurement).unescape(),
+            (true, Some(default_measurement)) => default_measurement.clone(),
+            (true, None) => return Err(ParseError::MissingMeasurement.into()),
+        };
 
         let tag_set = chars.collect::<String>();
-        let tags = match !tag_set.is_empty() {
-            true => Some(LineProtocol::parse_set::<TagKey, TagValue>(&tag_set)?),
+        let mut tags = match !tag_set.is_empty() {
+            true => Some(LineProtocol::parse_set::<TagKey, TagValue>(
+                &tag_set, None, false, None,
+            )?),
             false => None,
         };
 
+        if options.strip_tag_quotes {
+            for value in tags.iter_mut().flat_map(|tags| tags.values_mut()) {
+                if let Some(unquoted) = value
+                    .0
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                {
+                    *value = TagValue::from(unquoted);
+                }
+            }
+        }
+
         Ok((measurement, tags))
     }
 
@@ -178,58 +436,205 @@ impl LineProtocol {
     /// # Args
     /// * `line` - A InfluxDB line protocol line
     pub fn parse_line(line: &str) -> Result<Self> {
-        // Trim away leading and trailing whitespace
-        let line = line.trim();
+        LineProtocol::parse_line_with(line, &ParseOptions::default())
+    }
+
+    /// Parse a single line protocol line from bytes into the [LineProtocol]
+    /// struct, e.g., straight from a network buffer
+    ///
+    /// The bytes are validated as UTF-8 before being parsed like
+    /// [LineProtocol::parse_line]
+    ///
+    /// # Example
+    /// ```rust
+    /// let n = socket.recv(&mut buffer).unwrap();
+    /// let parsed_line = LineProtocol::parse_bytes(&buffer[..n]).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `input` - A InfluxDB line protocol line as UTF-8 bytes
+    pub fn parse_bytes(input: &[u8]) -> Result<Self> {
+        let line = std::str::from_utf8(input).map_err(ParseError::InvalidUtf8)?;
+        LineProtocol::parse_line(line)
+    }
+
+    /// Parse a single line protocol line into the [LineProtocol] struct,
+    /// substituting the provided measurement name if the line does not
+    /// include one
+    ///
+    /// A line without a measurement name either starts with the tag set, e.g.,
+    /// `,tag=value field=true`, or with a space followed by the field set,
+    /// e.g., ` field=true`
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = ",tag=value field=true 1729270461612452700";
+    /// let parsed_line =
+    ///     LineProtocol::parse_line_with_default_measurement(line, "measurement").unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `line` - A InfluxDB line protocol line
+    /// * `default` - The measurement name used if the line does not include one
+    pub fn parse_line_with_default_measurement<T>(line: &str, default: T) -> Result<Self>
+    where
+        T: Into<Measurement>,
+    {
+        let options = ParseOptions::new().default_measurement(default);
+        LineProtocol::parse_line_with(line, &options)
+    }
+
+    /// Parse a single line protocol line into the [LineProtocol] struct using
+    /// the provided [ParseOptions]
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = "measurement,tag=\"value\" field=true 1729270461612452700";
+    /// let options = ParseOptions::new().strip_tag_quotes(true);
+    /// let parsed_line = LineProtocol::parse_line_with(line, &options).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `line` - A InfluxDB line protocol line
+    /// * `options` - Options used when parsing the line
+    pub fn parse_line_with(line: &str, options: &ParseOptions) -> Result<Self> {
+        LineProtocol::parse_line_ordered(line, options, None)
+    }
+
+    /// Parse a single line protocol line into the [LineProtocol] struct and
+    /// return the field keys in the order they appear in the line
+    ///
+    /// The fields of a [LineProtocol] are unordered, use the returned keys to
+    /// rebuild the line with its original field order
+    ///
+    /// # Example
+    /// ```rust
+    /// let line = "measurement b=1i,a=2i,c=3i";
+    /// let (parsed_line, order) = LineProtocol::parse_line_with_order(line).unwrap();
+    /// // order: [b, a, c]
+    /// ```
+    ///
+    /// # Args
+    /// * `line` - A InfluxDB line protocol line
+    pub fn parse_line_with_order(line: &str) -> Result<(Self, Vec<FieldKey>)> {
+        let mut order = Vec::new();
+        let line_protocol =
+            LineProtocol::parse_line_ordered(line, &ParseOptions::default(), Some(&mut order))?;
+        Ok((line_protocol, order))
+    }
+
+    /// Parses a single line, recording the field key order if requested
+    fn parse_line_ordered(
+        line: &str,
+        options: &ParseOptions,
+        mut order: Option<&mut Vec<FieldKey>>,
+    ) -> Result<Self> {
+        // Files exported with a UTF-8 byte order mark (BOM) start with it
+        let line = line.strip_prefix(BOM).unwrap_or(line);
+
+        // Trim away leading and trailing whitespace. If a default measurement is
+        // given a leading space is kept as it denotes a missing measurement name
+        let line = match options.default_measurement.is_some() {
+            true => line.trim_end().trim_start_matches(['\n', '\r']),
+            false => line.trim(),
+        };
 
         // Comment line
-        if line.starts_with("#") {
+        if line.trim_start().starts_with("#") {
             return Err(ParseError::CommentLine.into());
         }
 
         // Can't parse empty lines
-        if line.is_empty() {
+        if line.trim_start().is_empty() {
             return Err(ParseError::EmptyLine.into());
         }
 
+        // A \r\n line ending is trimmed away, any other carriage return would end
+        // up in a value
+        if line.contains('\r') {
+            return Err(ParseError::StrayCarriageReturn.into());
+        }
+
         let mut chars = line.chars();
 
         // Parse measurement and tags
-        let identifiers = LineProtocol::parse_part(&mut chars);
-        let (measurement, tags) = LineProtocol::parse_identifiers(identifiers)?;
+        let identifiers = LineProtocol::parse_part(&mut chars, options);
+        let (measurement, tags) = LineProtocol::parse_identifiers(identifiers, options)?;
 
         // Parse field set
-        let field_set = LineProtocol::parse_part(&mut chars);
+        let field_set = LineProtocol::parse_part(&mut chars, options);
         if field_set.is_empty() {
             return Err(ParseError::MissingFields.into());
         }
 
-        let fields = LineProtocol::parse_set::<FieldKey, FieldValue>(&field_set)?;
+        // A field set always contains atleast one key value pair. Without an
+        // equals sign the part is a lone timestamp, e.g., `m,tag=v 12345`
+        if !field_set.contains('=') {
+            return Err(ParseError::MissingFields.into());
+        }
+
+        let mut fields = LineProtocol::parse_set::<FieldKey, FieldValue>(
+            &field_set,
+            options.custom_field_parser.as_deref(),
+            options.strict_field_values,
+            order.as_deref_mut(),
+        )?;
+
+        let time_field = FieldKey::from("time");
+        match &options.time_field_policy {
+            TimeFieldPolicy::Keep => {}
+            TimeFieldPolicy::Reject if fields.contains_key(&time_field) => {
+                return Err(ParseError::TimeField.into());
+            }
+            TimeFieldPolicy::Reject => {}
+            TimeFieldPolicy::RenameTo(key) => {
+                if let Some(value) = map_remove(&mut fields, &time_field) {
+                    fields.insert(FieldKey::from(key), value);
 
-        // Timestamp is the only part remaining
-        let timestamp = chars.collect::<String>();
-        let timestamp = match !timestamp.is_empty() {
+                    if let Some(order) = order {
+                        if let Some(position) = order.iter().position(|k| *k == time_field) {
+                            order[position] = FieldKey::from(key);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Timestamp is the only part remaining, anything after it is invalid
+        let remainder = chars.collect::<String>();
+        let timestamp = match !remainder.is_empty() {
             true => {
+                let (timestamp, trailing) = remainder
+                    .split_once(|char| options.is_delimiter(char))
+                    .unwrap_or((&remainder, ""));
+
                 let timestamp = match timestamp.parse::<i64>() {
                     Ok(timestamp) => timestamp,
                     Err(_) => return Err(ParseError::InvalidTimestamp.into()),
                 };
+
+                if !trailing.is_empty() {
+                    return Err(ParseError::TrailingData(trailing.to_string()).into());
+                }
                 Some(timestamp)
             }
             false => None,
         };
 
         let line_protocol = Self {
-            measurement: Measurement::from(measurement),
+            measurement,
             tags,
             fields,
             timestamp,
+            precision: TimestampPrecision::Nanoseconds,
         };
         Ok(line_protocol)
     }
 
     /// Parse a vector of lines
     ///
-    /// Empty lines and comment lines are silently ignored
+    /// Empty lines and comment lines are silently ignored. Duplicate data
+    /// points are merged, see [DedupPolicy::MergeFields]
     ///
     /// # Example
     /// ```rust
@@ -245,8 +650,33 @@ impl LineProtocol {
     /// # Args
     /// * `lines` - An array of InfluxDB line protocol lines
     pub fn parse_vec(lines: Vec<&str>) -> Result<Vec<Self>> {
-        let mut parsed_lines: Vec<LineProtocol> = Vec::new();
-        for line in lines {
+        LineProtocol::parse_vec_with(lines, DedupPolicy::MergeFields)
+    }
+
+    /// Parse a vector of lines handling duplicate data points according to the
+    /// provided [DedupPolicy]
+    ///
+    /// Empty lines and comment lines are silently ignored
+    ///
+    /// # Example
+    /// ```rust
+    /// let lines = vec![
+    ///     "measurement,tag=value field=1i 1729270461612452700",
+    ///     "measurement,tag=value field=2i 1729270461612452700",
+    /// ];
+    ///
+    /// let parsed = LineProtocol::parse_vec_with(lines, DedupPolicy::KeepAll).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `lines` - An array of InfluxDB line protocol lines
+    /// * `policy` - What to do with duplicate data points
+    pub fn parse_vec_with(lines: Vec<&str>, policy: DedupPolicy) -> Result<Vec<Self>> {
+        // The index of the line each data point was parsed from
+        let mut parsed_lines: Vec<(usize, LineProtocol)> = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            let line = line.strip_prefix(BOM).unwrap_or(line);
+
             // Ignore comment lines
             if line.starts_with("#") {
                 continue;
@@ -257,16 +687,130 @@ impl LineProtocol {
                 continue;
             }
 
-            // If the line protocol has been parsed earlier but is a duplicate we just add
-            // the fields value to the original but favor the latter
-            let parsed_line = LineProtocol::parse_line(line)?;
-            match parsed_lines.iter_mut().find(|l| **l == parsed_line) {
+            // Report the line number (starting at 1) the error occured on
+            let parsed_line = LineProtocol::parse_line(line).map_err(|e| at_line(index, e))?;
+
+            if policy == DedupPolicy::KeepAll {
+                parsed_lines.push((index, parsed_line));
+                continue;
+            }
+
+            let duplicate = parsed_lines
+                .iter_mut()
+                .find(|(_, l)| l.same_series(&parsed_line));
+
+            match (duplicate, policy) {
+                // If the line protocol has been parsed earlier but is a duplicate we just add
+                // the fields value to the original but favor the latter
+                (Some((_, lp)), DedupPolicy::MergeFields) => lp.fields.extend(parsed_line.fields),
+                (Some((first, _)), _) => {
+                    let e = ParseError::DuplicatePoint(*first + 1);
+                    return Err(at_line(index, e.into()).into());
+                }
+                (None, _) => parsed_lines.push((index, parsed_line)),
+            }
+        }
+
+        Ok(parsed_lines.into_iter().map(|(_, lp)| lp).collect())
+    }
+
+    /// Parse a vector of lines without failing on invalid lines
+    ///
+    /// Same as [LineProtocol::parse_vec] except a line which fails to parse
+    /// is skipped instead of failing the whole vector. The zero-based index of
+    /// each failed line is returned together with its error
+    ///
+    /// # Example
+    /// ```rust
+    /// let lines = vec![
+    ///     "measurement,tag=value field=\"value\"",
+    ///     "measurement,tag=value field=true timestamp",
+    /// ];
+    ///
+    /// let (parsed, errors) = LineProtocol::parse_vec_lenient(lines);
+    /// for (index, error) in errors {
+    ///     println!("skipped line {index}: {error}");
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `lines` - An array of InfluxDB line protocol lines
+    pub fn parse_vec_lenient(lines: Vec<&str>) -> (Vec<Self>, Vec<(usize, LineProtocolError)>) {
+        let mut parsed_lines: Vec<LineProtocol> = Vec::new();
+        let mut errors = Vec::new();
+        for (index, line) in lines.into_iter().enumerate() {
+            let line = line.strip_prefix(BOM).unwrap_or(line);
+
+            // Ignore comment and empty lines
+            if line.starts_with("#") || line.is_empty() {
+                continue;
+            }
+
+            let parsed_line = match LineProtocol::parse_line(line) {
+                Ok(parsed_line) => parsed_line,
+                Err(e) => {
+                    errors.push((index, e));
+                    continue;
+                }
+            };
+
+            match parsed_lines
+                .iter_mut()
+                .find(|l| l.same_series(&parsed_line))
+            {
                 Some(lp) => lp.fields.extend(parsed_line.fields),
                 None => parsed_lines.push(parsed_line),
             }
         }
 
-        Ok(parsed_lines)
+        (parsed_lines, errors)
+    }
+
+    /// Parse a vector of lines keeping comment lines and empty lines, e.g., to
+    /// rewrite a file without losing its comments
+    ///
+    /// Each line produces exactly one [LineEntry] in the same order. Unlike
+    /// [LineProtocol::parse_vec] duplicate data points are kept as is
+    ///
+    /// # Example
+    /// ```rust
+    /// let lines = vec![
+    ///     "# Sensor readings",
+    ///     "",
+    ///     "measurement,tag=value field=1i 1729270461612452700",
+    /// ];
+    ///
+    /// for entry in LineProtocol::parse_vec_preserving(lines).unwrap() {
+    ///     match entry {
+    ///         LineEntry::Point(point) => println!("{}", point.build().unwrap()),
+    ///         LineEntry::Comment(comment) => println!("#{comment}"),
+    ///         LineEntry::Blank => println!(),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `lines` - An array of InfluxDB line protocol lines
+    pub fn parse_vec_preserving(lines: Vec<&str>) -> Result<Vec<LineEntry>> {
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let line = line.strip_prefix(BOM).unwrap_or(line);
+
+                if let Some(comment) = line.strip_prefix("#") {
+                    return Ok(LineEntry::Comment(comment.to_string()));
+                }
+
+                if line.trim().is_empty() {
+                    return Ok(LineEntry::Blank);
+                }
+
+                // Report the line number (starting at 1) the error occured on
+                let parsed_line = LineProtocol::parse_line(line).map_err(|e| at_line(index, e))?;
+                Ok(LineEntry::Point(parsed_line))
+            })
+            .collect()
     }
 
     /// Parse multiple lines seprated by a newline (\n)
@@ -290,6 +834,115 @@ impl LineProtocol {
         let parsed_lines = LineProtocol::parse_vec(lines.lines().collect())?;
         Ok(parsed_lines)
     }
+
+    /// Parse multiple lines seperated by a newline (\n) into chunks of up to
+    /// `chunk_size` data points, e.g., to write one chunk per request
+    ///
+    /// Empty lines and comment lines are skipped and do not count towards the
+    /// size of a chunk. Unlike [LineProtocol::parse_lines] duplicate data
+    /// points are not merged. A line which fails to parse yields an error
+    /// mentioning the line number in place of the chunk it is part of, after
+    /// which the iterator ends. The data points of that chunk parsed before the
+    /// invalid line are discarded
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0
+    ///
+    /// # Example
+    /// ```rust
+    /// for chunk in LineProtocol::parse_chunks(&lines, 5000) {
+    ///     client.write(chunk.unwrap());
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `input` - Multiple InfluxDB line protocol lines seperated by a newline
+    /// * `chunk_size` - The maximum amount of data points in a chunk
+    pub fn parse_chunks(
+        input: &str,
+        chunk_size: usize,
+    ) -> impl Iterator<Item = Result<Vec<LineProtocol>>> + '_ {
+        assert!(chunk_size > 0, "chunk size must be greater than 0");
+
+        let mut lines = input.lines().enumerate();
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+
+            let mut chunk = Vec::with_capacity(chunk_size);
+            for (index, line) in lines.by_ref() {
+                match LineProtocol::parse_line(line) {
+                    Ok(parsed_line) => chunk.push(parsed_line),
+                    Err(LineProtocolError::ParserError(
+                        ParseError::CommentLine | ParseError::EmptyLine,
+                    )) => continue,
+                    Err(e) => {
+                        failed = true;
+                        return Some(Err(at_line(index, e).into()));
+                    }
+                }
+
+                if chunk.len() == chunk_size {
+                    break;
+                }
+            }
+
+            (!chunk.is_empty()).then_some(Ok(chunk))
+        })
+    }
+
+    /// Parse lines from a reader one at a time, e.g., to parse a file which
+    /// does not fit in memory
+    ///
+    /// Empty lines and comment lines are skipped. A line which fails to parse
+    /// yields an error mentioning the line number and a line which fails to be
+    /// read yields a [ParseError::Io] error. An error does not end the
+    /// iterator, the remaining lines can still be parsed
+    ///
+    /// # Example
+    /// ```rust
+    /// let file = BufReader::new(File::open("points.lp").unwrap());
+    /// for parsed_line in LineProtocol::parse_reader(file) {
+    ///     let parsed_line = parsed_line.unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Args
+    /// * `reader` - The source of the line protocol lines
+    pub fn parse_reader<R>(reader: R) -> impl Iterator<Item = Result<LineProtocol>>
+    where
+        R: BufRead,
+    {
+        reader.lines().enumerate().filter_map(|(index, line)| {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(ParseError::Io(e).into())),
+            };
+
+            match LineProtocol::parse_line(&line) {
+                Ok(parsed_line) => Some(Ok(parsed_line)),
+                Err(LineProtocolError::ParserError(
+                    ParseError::CommentLine | ParseError::EmptyLine,
+                )) => None,
+                Err(e) => Some(Err(at_line(index, e).into())),
+            }
+        })
+    }
+}
+
+/// Wrap an error with the line number (starting at 1) it occured on
+fn at_line(index: usize, e: LineProtocolError) -> ParseError {
+    let source: BoxError = match e {
+        LineProtocolError::ParserError(e) => e.into(),
+        e => e.into(),
+    };
+
+    ParseError::AtLine {
+        line: index + 1,
+        source,
+    }
 }
 
 #[cfg(test)]
@@ -299,7 +952,7 @@ mod test {
     #[test]
     fn test_parser_valid_missing_tags() {
         let line = "measurement field=\"value\" 1729270461612452700";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -312,7 +965,7 @@ mod test {
     #[test]
     fn test_parser_valid_missing_timestamp() {
         let line = "measurement,tag=value field=\"value\"";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
@@ -328,14 +981,14 @@ mod test {
                     \\\"bar\\\"}\",field3=\"[\\\"hello\\\", \
                     \\\"world\\\"]\",field4=true,field5=10,field6=10i,field7=0.5 \
                     1729270461612452700";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_ok());
 
         let parsed = result.unwrap();
         let expected = LineProtocol::new("measurement")
             .add_tag("tag1", "value")
             .add_tag("tag2", "value")
-            .add_field("field", "value")
+            .add_field("field1", "value")
             .add_field("field2", "{\"foo\": \"bar\"}")
             .add_field("field3", "[\"hello\", \"world\"]")
             .add_field("field4", true)
@@ -349,49 +1002,715 @@ mod test {
     #[test]
     fn test_parser_comment_line_is_err() {
         let line = "# this is a comment line";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_empty_line_is_err() {
         let line = "";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_missing_measurement_is_err() {
         let line = ",tag=value field=\"value\"";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_missing_field_set_is_err() {
         let line = "measurement,tag=value 1729270461612452800";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_missing_uneven_tag_set_is_err() {
         let line = "measurement,tag= 1729270461612452800";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_missing_uneven_field_set_is_err() {
         let line = "measurement field= 1729270461612452800";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
 
     #[test]
     fn test_parser_missing_invalid_timestamp_is_err() {
         let line = "measurement field=\"value\" timestamp";
-        let result = LineProtocol::parse_line(&line);
+        let result = LineProtocol::parse_line(line);
         assert!(result.is_err())
     }
+
+    #[test]
+    fn test_parser_default_measurement() {
+        let expected = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", true)
+            .with_timestamp(1729270461612452700i64);
+
+        let line = ",tag=value field=true 1729270461612452700";
+        let result = LineProtocol::parse_line_with_default_measurement(line, "measurement");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), expected);
+
+        let line = " field=true";
+        let result = LineProtocol::parse_line_with_default_measurement(line, "measurement");
+        assert!(result.is_ok());
+
+        let parsed = result.unwrap();
+        assert_eq!(parsed.measurement, Measurement::from("measurement"));
+        assert!(parsed.tags.is_none());
+
+        // Measurement in the line takes precedence over the default
+        let line = "other,tag=value field=true 1729270461612452700";
+        let result = LineProtocol::parse_line_with_default_measurement(line, "measurement");
+        assert_eq!(result.unwrap().measurement, Measurement::from("other"));
+
+        // Strict parsing still requires a measurement
+        let line = ",tag=value field=true 1729270461612452700";
+        assert!(LineProtocol::parse_line(line).is_err());
+    }
+
+    #[test]
+    fn test_parser_string_field_round_trip() {
+        let strings = vec![
+            "line\\nbreak",
+            "tab\\tchar",
+            "back\\\\slash",
+            "quote\\\"d",
+            "\\",
+            "\\\\",
+            "\"",
+            "\\\"",
+            "ends with\\",
+            "C:\\path\\to\\file",
+            "{\"json\": \"a\\nb\\t\\\"c\\\"\"}",
+            "mixed \\\" and \\\\\" and \"\\",
+        ];
+
+        for string in strings {
+            let line = LineProtocol::new("measurement")
+                .add_field("field", string)
+                .add_field("other", true)
+                .build()
+                .unwrap();
+
+            let parsed = LineProtocol::parse_line(&line).unwrap();
+            assert_eq!(
+                parsed.get_field("field"),
+                Some(FieldValue::from(string)),
+                "round trip of {string:?}"
+            );
+            assert_eq!(parsed.get_field("other"), Some(FieldValue::from(true)));
+        }
+    }
+
+    #[test]
+    fn test_parser_strip_tag_quotes() {
+        let line = "m,host=\"a\" f=1i";
+
+        let options = ParseOptions::new().strip_tag_quotes(true);
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(parsed.get_tag("host"), Some(TagValue::from("a")));
+        assert_eq!(parsed.build().unwrap(), "m,host=a f=1i");
+
+        // Quotes are kept by default
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(parsed.get_tag("host"), Some(TagValue::from("\"a\"")));
+    }
+
+    #[test]
+    fn test_parser_whitespace_delimiters() {
+        let line = "measurement,tag=value\tfield=1i\t1729270461612452700";
+
+        let options = ParseOptions::new().whitespace_delimiters(true);
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(
+            parsed.build().unwrap(),
+            "measurement,tag=value field=1i 1729270461612452700"
+        );
+
+        // Only a space is a delimiter by default
+        assert!(LineProtocol::parse_line(line).is_err());
+    }
+
+    #[test]
+    fn test_parser_time_field_keep() {
+        let line = "measurement time=1i,field=2i 1729270461612452700";
+
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(parsed.get_field("time"), Some(FieldValue::Integer(1)));
+
+        let options = ParseOptions::new().handle_time_field(TimeFieldPolicy::Keep);
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(parsed.get_field("time"), Some(FieldValue::Integer(1)));
+    }
+
+    #[test]
+    fn test_parser_time_field_reject() {
+        let options = ParseOptions::new().handle_time_field(TimeFieldPolicy::Reject);
+
+        let line = "measurement time=1i,field=2i 1729270461612452700";
+        let result = LineProtocol::parse_line_with(line, &options);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::TimeField))
+        ));
+
+        let line = "measurement field=2i 1729270461612452700";
+        assert!(LineProtocol::parse_line_with(line, &options).is_ok());
+    }
+
+    #[test]
+    fn test_parser_time_field_rename() {
+        let options = ParseOptions::new()
+            .handle_time_field(TimeFieldPolicy::RenameTo("time_field".to_string()));
+
+        let line = "measurement time=1i,field=2i 1729270461612452700";
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(parsed.get_field("time"), None);
+        assert_eq!(parsed.get_field("time_field"), Some(FieldValue::Integer(1)));
+        assert_eq!(parsed.get_field("field"), Some(FieldValue::Integer(2)));
+    }
+
+    #[test]
+    fn test_parser_error_source_chain() {
+        use std::{error::Error, num::ParseIntError};
+
+        let line = "measurement field=18446744073709551616u";
+        let error = LineProtocol::parse_line(line).unwrap_err();
+        assert!(matches!(
+            error,
+            LineProtocolError::ParserError(ParseError::InvalidSet(_))
+        ));
+
+        // LineProtocolError -> ParseError
+        let source = error.source().unwrap();
+        assert!(matches!(
+            source.downcast_ref::<ParseError>(),
+            Some(ParseError::InvalidSet(_))
+        ));
+
+        // ParseError -> conversion error context
+        let source = source.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            "number 18446744073709551616u is not a valid unsigned integer"
+        );
+
+        // Conversion error context -> underlying conversion error
+        let source = source.source().unwrap();
+        assert!(source.downcast_ref::<ParseIntError>().is_some());
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn test_parser_error_source_chain_without_cause() {
+        use std::error::Error;
+
+        let line = "measurement field=";
+        let error = LineProtocol::parse_line(line).unwrap_err();
+
+        let source = error.source().unwrap();
+        assert!(source.downcast_ref::<ParseError>().is_some());
+
+        let source = source.source().unwrap();
+        assert_eq!(source.to_string(), "set contains uneven amount of values");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn test_parser_leading_bom() {
+        let line = "\u{FEFF}measurement,tag=value field=1i";
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(parsed.measurement, Measurement::from("measurement"));
+
+        let lines = "\u{FEFF}# comment\nmeasurement field=1i\n\u{FEFF}measurement field=2i 1";
+        let parsed = LineProtocol::parse_lines(lines).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(parsed
+            .iter()
+            .all(|lp| lp.measurement == Measurement::from("measurement")));
+    }
+
+    #[test]
+    fn test_parser_vec_lenient() {
+        let lines = vec![
+            "# comment",
+            "measurement,tag=value field=1i 1729270461612452700",
+            "measurement,tag=value field=true timestamp",
+            "",
+            "measurement field=",
+            "measurement,tag=value field=2i 1729270461612452800",
+        ];
+
+        let (parsed, errors) = LineProtocol::parse_vec_lenient(lines.clone());
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].get_field("field"), Some(FieldValue::Integer(1)));
+        assert_eq!(parsed[1].get_field("field"), Some(FieldValue::Integer(2)));
+
+        let indices = errors.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        assert_eq!(indices, vec![2, 4]);
+        assert!(matches!(
+            errors[0].1,
+            LineProtocolError::ParserError(ParseError::InvalidTimestamp)
+        ));
+
+        // The strict variant fails on the first invalid line
+        assert!(LineProtocol::parse_vec(lines).is_err());
+    }
+
+    #[test]
+    fn test_parser_timestamp_without_fields_is_missing_fields() {
+        for line in ["m,tag=v 12345", "m 12345", "m,tag=v"] {
+            let result = LineProtocol::parse_line(line);
+            assert!(
+                matches!(
+                    result,
+                    Err(LineProtocolError::ParserError(ParseError::MissingFields))
+                ),
+                "{line}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parser_lines_error_includes_line_number() {
+        let lines = [
+            "measurement field=1i 1729270461612452700",
+            "# comment",
+            "",
+            "measurement field=2i timestamp",
+        ]
+        .join("\n");
+
+        let error = LineProtocol::parse_lines(&lines).unwrap_err();
+        assert!(matches!(
+            error,
+            LineProtocolError::ParserError(ParseError::AtLine { line: 4, .. })
+        ));
+
+        let LineProtocolError::ParserError(error) = error else {
+            unreachable!()
+        };
+        assert_eq!(
+            error.to_string(),
+            "error on line 4: timestamp is not a valid number"
+        );
+    }
+
+    #[test]
+    fn test_parser_duplicate_tag_key_is_err() {
+        let result = LineProtocol::parse_line("m,host=a,host=b f=1i");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::DuplicateKey(key))) if key == "host"
+        ));
+    }
+
+    #[test]
+    fn test_parser_duplicate_field_key_is_err() {
+        let result = LineProtocol::parse_line("m,host=a f=1i,f=2i");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::DuplicateKey(key))) if key == "f"
+        ));
+
+        // A tag and a field can share the same key
+        assert!(LineProtocol::parse_line("m,f=a f=1i").is_ok());
+    }
+
+    #[test]
+    fn test_parser_custom_field_parser() {
+        // Decode hex encoded field values into a string
+        let options = ParseOptions::new().custom_field_parser(|value| {
+            let hex = value.strip_prefix("0x")?;
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect::<Option<Vec<_>>>()?;
+            String::from_utf8(bytes).ok().map(FieldValue::String)
+        });
+
+        let line = "measurement hex=0x68656c6c6f,other=1i,invalid=0xZZ 1729270461612452700";
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(
+            parsed.get_field("hex"),
+            Some(FieldValue::String("hello".to_string()))
+        );
+        assert_eq!(parsed.get_field("other"), Some(FieldValue::Integer(1)));
+        assert_eq!(
+            parsed.get_field("invalid"),
+            Some(FieldValue::String("0xZZ".to_string()))
+        );
+
+        // Without the custom parser the value is kept as is
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(
+            parsed.get_field("hex"),
+            Some(FieldValue::String("0x68656c6c6f".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parser_chunks() {
+        let lines = [
+            "# comment",
+            "measurement field=1i 1",
+            "measurement field=2i 2",
+            "",
+            "measurement field=3i 3",
+            "measurement field=4i 4",
+            "# comment",
+            "measurement field=5i 5",
+        ]
+        .join("\n");
+
+        let chunks = LineProtocol::parse_chunks(&lines, 2)
+            .map(|chunk| {
+                chunk
+                    .unwrap()
+                    .iter()
+                    .map(|lp| lp.timestamp.unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+
+        assert_eq!(LineProtocol::parse_chunks("# comment\n", 2).count(), 0);
+    }
+
+    #[test]
+    fn test_parser_chunks_invalid_line_is_err() {
+        let lines = "measurement field=1i 1\nmeasurement field=2i 2\nmeasurement field=3i \
+                     timestamp\nmeasurement field=4i 4";
+
+        let mut chunks = LineProtocol::parse_chunks(lines, 2);
+        assert_eq!(chunks.next().unwrap().unwrap().len(), 2);
+        assert!(matches!(
+            chunks.next(),
+            Some(Err(LineProtocolError::ParserError(ParseError::AtLine {
+                line: 3,
+                ..
+            })))
+        ));
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn test_parser_escaped_comma_and_quoted_equals() {
+        let line = r#"measurement,tag=a\,b field="a=b,c""#;
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(parsed.get_tag("tag"), Some(TagValue::from("a,b")));
+        assert_eq!(
+            parsed.get_field("field"),
+            Some(FieldValue::String("a=b,c".to_string()))
+        );
+        assert_eq!(parsed.build().unwrap(), line);
+    }
+
+    #[test]
+    fn test_parser_reader() {
+        let input = [
+            "# comment",
+            "measurement field=1i 1",
+            "",
+            "measurement field=2i timestamp",
+            "measurement field=3i 3",
+        ]
+        .join("\n");
+
+        let parsed = LineProtocol::parse_reader(input.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].as_ref().unwrap().timestamp, Some(1));
+        assert!(matches!(
+            parsed[1],
+            Err(LineProtocolError::ParserError(ParseError::AtLine {
+                line: 4,
+                ..
+            }))
+        ));
+        assert_eq!(parsed[2].as_ref().unwrap().timestamp, Some(3));
+    }
+
+    #[test]
+    fn test_parser_reader_io_error() {
+        // Invalid UTF-8 fails to be read as a line
+        let input: &[u8] = b"measurement field=1i 1\n\xff\nmeasurement field=3i 3";
+
+        let parsed = LineProtocol::parse_reader(input).collect::<Vec<_>>();
+        assert_eq!(parsed.len(), 3);
+        assert!(matches!(
+            parsed[1],
+            Err(LineProtocolError::ParserError(ParseError::Io(_)))
+        ));
+        assert!(parsed[2].is_ok());
+    }
+
+    #[test]
+    fn test_parser_line_with_order() {
+        let line = "measurement,tag=value zeta=1i,alpha=\"a,b\",mid\\ dle=true,beta=0.5 1";
+        let (parsed_line, order) = LineProtocol::parse_line_with_order(line).unwrap();
+
+        let expected: Vec<FieldKey> = vec![
+            "zeta".into(),
+            "alpha".into(),
+            "mid dle".into(),
+            "beta".into(),
+        ];
+        assert_eq!(order, expected);
+        assert_eq!(parsed_line.field_count(), 4);
+        assert_eq!(parsed_line, LineProtocol::parse_line(line).unwrap());
+    }
+
+    #[test]
+    fn test_parser_trailing_data() {
+        let result = LineProtocol::parse_line("measurement field=1i 123 extra data");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::TrailingData(data))) if data == "extra data"
+        ));
+
+        let result = LineProtocol::parse_line_ref("measurement field=1i 123 extra");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::TrailingData(data))) if data == "extra"
+        ));
+
+        // An invalid timestamp is still reported as such
+        let result = LineProtocol::parse_line("measurement field=1i abc extra");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::InvalidTimestamp))
+        ));
+
+        let result = LineProtocol::parse_line("measurement field=1i  123");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::InvalidTimestamp))
+        ));
+    }
+
+    #[test]
+    fn test_parser_integer_overflow() {
+        let result = LineProtocol::parse_line("measurement field=9223372036854775808i");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::IntegerOverflow(s))) if s == "9223372036854775808i"
+        ));
+
+        let result = LineProtocol::parse_line_ref("measurement field=9223372036854775808i");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::IntegerOverflow(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_parser_crlf_line_endings() {
+        let input = "measurement,tag=value field=\"value\",other=1i \
+                     1729270461612452700\r\nmeasurement,tag=value2 field=\"value\"\r\n# \
+                     comment\r\n\r\nmeasurement field=true 1729270461612452701\r\n";
+
+        let expected = [
+            "measurement,tag=value field=\"value\",other=1i 1729270461612452700",
+            "measurement,tag=value2 field=\"value\"",
+            "measurement field=true 1729270461612452701",
+        ];
+
+        let parsed = LineProtocol::parse_lines(input).unwrap();
+        let built = parsed
+            .iter()
+            .map(|lp| lp.build().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(built, expected);
+
+        let parsed = LineProtocol::parse_chunks(input, 2)
+            .flat_map(|chunk| chunk.unwrap())
+            .collect::<Vec<_>>();
+        let built = parsed
+            .iter()
+            .map(|lp| lp.build().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(built, expected);
+
+        let parsed = LineProtocol::parse_reader(input.as_bytes())
+            .map(|lp| lp.unwrap().build().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(parsed, expected);
+
+        // A single line ending in \r\n
+        let parsed = LineProtocol::parse_line("measurement field=1i 1\r\n").unwrap();
+        assert_eq!(parsed.timestamp, Some(1));
+    }
+
+    #[test]
+    fn test_parser_stray_carriage_return_is_err() {
+        let lines = [
+            "measure\rment field=1i",
+            "measurement,tag=va\rlue field=1i",
+            "measurement field=\"va\rlue\"",
+            "measurement field=1i 12\r34",
+        ];
+
+        for line in lines {
+            assert!(matches!(
+                LineProtocol::parse_line(line),
+                Err(LineProtocolError::ParserError(
+                    ParseError::StrayCarriageReturn
+                ))
+            ));
+            assert!(matches!(
+                LineProtocol::parse_line_ref(line),
+                Err(LineProtocolError::ParserError(
+                    ParseError::StrayCarriageReturn
+                ))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parser_vec_with_dedup_policy() {
+        let lines = vec![
+            "measurement,tag=value field=1i,other=true 1729270461612452700",
+            "measurement,tag=other field=1i 1729270461612452700",
+            "# comment",
+            "measurement,tag=value field=2i 1729270461612452700",
+        ];
+
+        let parsed = LineProtocol::parse_vec_with(lines.clone(), DedupPolicy::MergeFields).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].get_field("field"), Some(FieldValue::Integer(2)));
+        assert_eq!(
+            parsed[0].get_field("other"),
+            Some(FieldValue::Boolean(true))
+        );
+        assert_eq!(parsed, LineProtocol::parse_vec(lines.clone()).unwrap());
+
+        let parsed = LineProtocol::parse_vec_with(lines.clone(), DedupPolicy::KeepAll).unwrap();
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].get_field("field"), Some(FieldValue::Integer(1)));
+        assert_eq!(parsed[2].get_field("field"), Some(FieldValue::Integer(2)));
+
+        let result = LineProtocol::parse_vec_with(lines, DedupPolicy::Error);
+        let Err(LineProtocolError::ParserError(ParseError::AtLine { line, source })) = result
+        else {
+            panic!("expected a duplicate error");
+        };
+        assert_eq!(line, 4);
+        assert!(matches!(
+            source.downcast_ref::<ParseError>(),
+            Some(ParseError::DuplicatePoint(1))
+        ));
+    }
+
+    #[test]
+    fn test_parser_strict_field_values() {
+        let options = ParseOptions::new().strict_field_values(true);
+
+        let line = "measurement a=t,b=FALSE,c=\"T\",d=10i,e=True 1729270461612452700";
+        let parsed = LineProtocol::parse_line_with(line, &options).unwrap();
+        assert_eq!(parsed.get_field("a"), Some(FieldValue::Boolean(true)));
+        assert_eq!(parsed.get_field("b"), Some(FieldValue::Boolean(false)));
+        assert_eq!(
+            parsed.get_field("c"),
+            Some(FieldValue::String("T".to_string()))
+        );
+        assert_eq!(parsed.get_field("d"), Some(FieldValue::Integer(10)));
+        assert_eq!(parsed.get_field("e"), Some(FieldValue::Boolean(true)));
+
+        let result = LineProtocol::parse_line_with("measurement field=NaN", &options);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::NonFiniteFloat(
+                _
+            )))
+        ));
+
+        for line in ["measurement field=tRUE", "measurement field=value"] {
+            let result = LineProtocol::parse_line_with(line, &options);
+            assert!(
+                matches!(
+                    result,
+                    Err(LineProtocolError::ParserError(ParseError::UnquotedString(
+                        _
+                    )))
+                ),
+                "{line}"
+            );
+
+            // Lenient parsing accepts the value
+            assert!(LineProtocol::parse_line(line).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_parser_parse_bytes() {
+        let line = "measurement,tag=value field=\"héllo\" 1729270461612452700";
+        let parsed = LineProtocol::parse_bytes(line.as_bytes()).unwrap();
+        assert_eq!(parsed, LineProtocol::parse_line(line).unwrap());
+
+        let result = LineProtocol::parse_bytes(b"measurement field=\"\xff\"");
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::InvalidUtf8(_)))
+        ));
+    }
+
+    #[test]
+    fn test_parser_parse_vec_preserving() {
+        let lines = vec![
+            "# Sensor readings",
+            "",
+            "measurement,tag=value field=1i 1729270461612452700",
+            "  ",
+            "measurement,tag=value field=2i 1729270461612452700",
+        ];
+
+        let entries = LineProtocol::parse_vec_preserving(lines.clone()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                LineEntry::Comment(" Sensor readings".to_string()),
+                LineEntry::Blank,
+                LineEntry::Point(LineProtocol::parse_line(lines[2]).unwrap()),
+                LineEntry::Blank,
+                LineEntry::Point(LineProtocol::parse_line(lines[4]).unwrap()),
+            ]
+        );
+
+        let result = LineProtocol::parse_vec_preserving(vec!["# comment", "measurement"]);
+        assert!(matches!(
+            result,
+            Err(LineProtocolError::ParserError(ParseError::AtLine {
+                line: 2,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_parser_backslash_in_string_field() {
+        // Only \\ and \" are escapes in a string field value, a backslash followed by
+        // any other character is kept as is
+        let line = r#"measurement path="C:\new\table",quote="say \"hi\"" 1"#;
+        let parsed = LineProtocol::parse_line(line).unwrap();
+        assert_eq!(
+            parsed.get_field("path"),
+            Some(FieldValue::String(r"C:\new\table".to_string()))
+        );
+        assert_eq!(
+            parsed.get_field("quote"),
+            Some(FieldValue::String(r#"say "hi""#.to_string()))
+        );
+    }
 }
@@ -0,0 +1,127 @@
+//! A [ValidPoint] is a data point which has passed validation
+//!
+//! Where [LineProtocol] is the mutable builder which may or may not build into
+//! a valid line protocol string, a [ValidPoint] is guaranteed to hold a valid
+//! data point. It can only be obtained through [LineProtocol::build_validated]
+//! or by converting a [LineProtocol] using [TryFrom], and only exposes read
+//! access
+
+use std::fmt::Display;
+
+use crate::{
+    element::{FieldKey, FieldValue, Measurement, TagKey, TagValue},
+    error::{LineProtocolError, Result},
+    LineProtocol, Map,
+};
+
+/// An immutable and validated data point
+#[derive(Debug, Clone)]
+pub struct ValidPoint {
+    point: LineProtocol,
+    line: String,
+}
+
+impl ValidPoint {
+    /// Get a reference of the measurement
+    pub fn measurement(&self) -> &Measurement {
+        &self.point.measurement
+    }
+
+    /// Get a reference of the tag set
+    pub fn tags(&self) -> Option<&Map<TagKey, TagValue>> {
+        self.point.tags.as_ref()
+    }
+
+    /// Get a reference of the field set. A valid data point always contains
+    /// atleast one field
+    pub fn fields(&self) -> &Map<FieldKey, FieldValue> {
+        &self.point.fields
+    }
+
+    /// Get the timestamp
+    pub fn timestamp(&self) -> Option<i64> {
+        self.point.timestamp
+    }
+
+    /// Get a reference of the validated data point
+    pub fn as_line_protocol(&self) -> &LineProtocol {
+        &self.point
+    }
+
+    /// Get a reference of the built line protocol string
+    pub fn as_str(&self) -> &str {
+        &self.line
+    }
+
+    /// Convert back into the mutable [LineProtocol] builder
+    pub fn into_inner(self) -> LineProtocol {
+        self.point
+    }
+}
+
+impl Display for ValidPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.line)
+    }
+}
+
+impl TryFrom<LineProtocol> for ValidPoint {
+    type Error = LineProtocolError;
+
+    fn try_from(point: LineProtocol) -> Result<Self> {
+        let line = point.build()?;
+        Ok(ValidPoint { point, line })
+    }
+}
+
+impl LineProtocol {
+    /// Validates the data point and converts it into a [ValidPoint]
+    ///
+    /// The validation is only performed once, after which the [ValidPoint]
+    /// can be cheaply converted into a line protocol string
+    ///
+    /// # Example
+    /// ```rust
+    /// let point = LineProtocol::new("measurement")
+    ///     .add_field("field", "value")
+    ///     .build_validated()
+    ///     .unwrap();
+    ///
+    /// let line = point.to_string();
+    /// ```
+    pub fn build_validated(&self) -> Result<ValidPoint> {
+        ValidPoint::try_from(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_point_build_validated() {
+        let line_protocol = LineProtocol::new("measurement")
+            .add_tag("tag", "value")
+            .add_field("field", "value")
+            .with_timestamp(1729270461612452700i64);
+
+        let result = line_protocol.build_validated();
+        assert!(result.is_ok());
+
+        let point = result.unwrap();
+        assert_eq!(point.to_string(), line_protocol.build().unwrap());
+        assert_eq!(point.as_str(), point.to_string());
+        assert_eq!(point.measurement(), &Measurement::from("measurement"));
+        assert_eq!(point.fields().len(), 1);
+        assert_eq!(point.timestamp(), Some(1729270461612452700));
+    }
+
+    #[test]
+    fn test_point_missing_fields_is_err() {
+        let result = LineProtocol::new("measurement").build_validated();
+        assert!(result.is_err());
+
+        let result = ValidPoint::try_from(LineProtocol::new("measurement"));
+        assert!(result.is_err());
+    }
+}
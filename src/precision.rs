@@ -0,0 +1,128 @@
+//! Timestamp precision
+//!
+//! InfluxDB does not store the precision of a timestamp in the line protocol
+//! itself, instead it is defined when writing the data points. By default the
+//! precision is nanoseconds
+
+/// The precision of a unix timestamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TimestampPrecision {
+    /// Nanosecond precision, e.g., `1729270461612452700`
+    #[default]
+    Nanoseconds,
+
+    /// Microsecond precision, e.g., `1729270461612452`
+    Microseconds,
+
+    /// Millisecond precision, e.g., `1729270461612`
+    Milliseconds,
+
+    /// Second precision, e.g., `1729270461`
+    Seconds,
+}
+
+impl TimestampPrecision {
+    /// Guess the precision of a timestamp based on the amount of digits
+    ///
+    /// This assumes the timestamp is somewhat close to the present date, e.g.,
+    /// a 10 digit timestamp is in seconds while a 19 digit timestamp is in
+    /// nanoseconds
+    ///
+    /// # Example
+    /// ```rust
+    /// let precision = TimestampPrecision::guess(1729270461);
+    /// assert_eq!(precision, TimestampPrecision::Seconds);
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp
+    pub fn guess(timestamp: i64) -> Self {
+        let digits = timestamp.unsigned_abs().checked_ilog10().unwrap_or(0) + 1;
+        match digits {
+            0..=11 => TimestampPrecision::Seconds,
+            12..=14 => TimestampPrecision::Milliseconds,
+            15..=17 => TimestampPrecision::Microseconds,
+            _ => TimestampPrecision::Nanoseconds,
+        }
+    }
+
+    /// The amount of nanoseconds in one unit of the precision
+    pub fn nanoseconds_per_unit(&self) -> i64 {
+        match self {
+            TimestampPrecision::Nanoseconds => 1,
+            TimestampPrecision::Microseconds => 1_000,
+            TimestampPrecision::Milliseconds => 1_000_000,
+            TimestampPrecision::Seconds => 1_000_000_000,
+        }
+    }
+
+    /// Convert a timestamp in this precision to another precision
+    ///
+    /// Converting to a coarser precision truncates the timestamp towards
+    /// zero. `None` is returned if the converted timestamp does not fit in an
+    /// i64
+    ///
+    /// # Example
+    /// ```rust
+    /// let timestamp =
+    ///     TimestampPrecision::Nanoseconds.convert(1729270461612452700, TimestampPrecision::Seconds);
+    /// assert_eq!(timestamp, Some(1729270461));
+    /// ```
+    ///
+    /// # Args
+    /// * `timestamp` - A unix timestamp in this precision
+    /// * `to` - The precision to convert the timestamp to
+    pub fn convert(&self, timestamp: i64, to: TimestampPrecision) -> Option<i64> {
+        let nanoseconds = i128::from(timestamp) * i128::from(self.nanoseconds_per_unit());
+        i64::try_from(nanoseconds / i128::from(to.nanoseconds_per_unit())).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_precision_guess() {
+        assert_eq!(
+            TimestampPrecision::guess(1729270461),
+            TimestampPrecision::Seconds
+        );
+        assert_eq!(
+            TimestampPrecision::guess(1729270461612),
+            TimestampPrecision::Milliseconds
+        );
+        assert_eq!(
+            TimestampPrecision::guess(1729270461612452),
+            TimestampPrecision::Microseconds
+        );
+        assert_eq!(
+            TimestampPrecision::guess(1729270461612452700),
+            TimestampPrecision::Nanoseconds
+        );
+        assert_eq!(TimestampPrecision::guess(0), TimestampPrecision::Seconds);
+    }
+
+    #[test]
+    fn test_precision_convert() {
+        let timestamp = 1729270461612452700;
+        let nanoseconds = TimestampPrecision::Nanoseconds;
+
+        assert_eq!(
+            nanoseconds.convert(timestamp, TimestampPrecision::Seconds),
+            Some(1729270461)
+        );
+        assert_eq!(
+            nanoseconds.convert(timestamp, TimestampPrecision::Milliseconds),
+            Some(1729270461612)
+        );
+        assert_eq!(
+            TimestampPrecision::Seconds.convert(1729270461, TimestampPrecision::Microseconds),
+            Some(1729270461000000)
+        );
+        assert_eq!(
+            TimestampPrecision::Seconds.convert(i64::MAX, TimestampPrecision::Nanoseconds),
+            None
+        );
+    }
+}
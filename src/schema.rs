@@ -0,0 +1,153 @@
+//! A schema declares the tags and fields a data point is required to have
+//!
+//! Use [LineProtocol::validate_against] to check a data point against a
+//! [Schema], e.g., to reject malformed data points before they are ingested
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    element::{FieldKey, FieldType, TagKey},
+    error::SchemaViolation,
+    LineProtocol,
+};
+
+/// The required tags and fields of a data point
+///
+/// Tags and fields not declared in the schema are allowed
+///
+/// # Example
+/// ```rust
+/// let schema = Schema::new()
+///     .require_tag("host")
+///     .require_field("value", FieldType::Float);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    tags: BTreeSet<TagKey>,
+    fields: BTreeMap<FieldKey, FieldType>,
+}
+
+impl Schema {
+    /// Create a new empty schema
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the data point to have a tag
+    ///
+    /// # Args
+    /// * `key` - The required tag key
+    pub fn require_tag<K>(mut self, key: K) -> Self
+    where
+        K: Into<TagKey>,
+    {
+        self.tags.insert(key.into());
+        self
+    }
+
+    /// Require the data point to have a field of the given type
+    ///
+    /// # Args
+    /// * `key` - The required field key
+    /// * `field_type` - The expected type of the field value
+    pub fn require_field<K>(mut self, key: K, field_type: FieldType) -> Self
+    where
+        K: Into<FieldKey>,
+    {
+        self.fields.insert(key.into(), field_type);
+        self
+    }
+}
+
+impl LineProtocol {
+    /// Validate the data point against a [Schema]
+    ///
+    /// All violations are reported, ordered by key with the tag violations
+    /// first
+    ///
+    /// # Example
+    /// ```rust
+    /// let schema = Schema::new()
+    ///     .require_tag("host")
+    ///     .require_field("value", FieldType::Float);
+    ///
+    /// let line_protocol = LineProtocol::new("measurement")
+    ///     .add_tag("host", "a")
+    ///     .add_field("value", 0.5);
+    /// line_protocol.validate_against(&schema).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `schema` - The schema the data point must conform to
+    pub fn validate_against(&self, schema: &Schema) -> Result<(), Vec<SchemaViolation>> {
+        let mut violations = Vec::new();
+
+        for key in &schema.tags {
+            if !self.contains_tag(key.clone()) {
+                violations.push(SchemaViolation::MissingTag(key.clone()));
+            }
+        }
+
+        for (key, expected) in &schema.fields {
+            match self.get_field_ref(key.clone()) {
+                None => violations.push(SchemaViolation::MissingField(key.clone())),
+                Some(value) if value.field_type() != *expected => {
+                    violations.push(SchemaViolation::TypeMismatch {
+                        key: key.clone(),
+                        expected: *expected,
+                        found: value.field_type(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_schema_validate() {
+        let schema = Schema::new()
+            .require_tag("host")
+            .require_field("value", FieldType::Float);
+
+        let conforming = LineProtocol::new("measurement")
+            .add_tag("host", "a")
+            .add_tag("region", "eu")
+            .add_field("value", 0.5)
+            .add_field("status", "ok");
+        assert!(conforming.validate_against(&schema).is_ok());
+
+        let violating = LineProtocol::new("measurement")
+            .add_tag("region", "eu")
+            .add_field("value", 1);
+        assert_eq!(
+            violating.validate_against(&schema),
+            Err(vec![
+                SchemaViolation::MissingTag("host".into()),
+                SchemaViolation::TypeMismatch {
+                    key: "value".into(),
+                    expected: FieldType::Float,
+                    found: FieldType::Integer,
+                },
+            ])
+        );
+
+        let missing = LineProtocol::new("measurement").add_field("status", "ok");
+        assert_eq!(
+            missing.validate_against(&schema),
+            Err(vec![
+                SchemaViolation::MissingTag("host".into()),
+                SchemaViolation::MissingField("value".into()),
+            ])
+        );
+    }
+}
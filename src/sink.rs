@@ -0,0 +1,121 @@
+//! Sinks are destinations data points can be written to
+//!
+//! A [FileSink] appends data points to a file, e.g., to keep a durable local
+//! buffer of data points before they are written to InfluxDB
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::LineProtocol;
+
+/// A file-backed sink appending one line protocol line per data point
+///
+/// Writes are buffered, call [FileSink::flush_sync] to make sure the data
+/// points are written to disk
+#[derive(Debug)]
+pub struct FileSink {
+    writer: BufWriter<File>,
+}
+
+impl FileSink {
+    /// Open a file for appending, creating it if it does not exist
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut sink = FileSink::open("points.lp").unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `path` - Path to the file
+    pub fn open<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileSink {
+            writer: BufWriter::new(file),
+        })
+    }
+
+    /// Build the data point and append it to the file followed by a newline
+    ///
+    /// A data point which fails to build is returned as an
+    /// [InvalidData](io::ErrorKind::InvalidData) error and nothing is written
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut sink = FileSink::open("points.lp").unwrap();
+    ///
+    /// let line_protocol = LineProtocol::new("measurement").add_field("field", "value");
+    /// sink.append(&line_protocol).unwrap();
+    /// ```
+    ///
+    /// # Args
+    /// * `lp` - The data point to append
+    pub fn append(&mut self, lp: &LineProtocol) -> io::Result<()> {
+        let line = lp
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Flush the buffered data points and fsync the file
+    ///
+    /// Call this once per batch to control how often the file is synced to
+    /// disk
+    pub fn flush_sync(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_all()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_sink_file_append() {
+        let path =
+            std::env::temp_dir().join(format!("influxlp-tools-sink-{}.lp", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut sink = FileSink::open(&path).unwrap();
+        for i in 0..3 {
+            let line_protocol = LineProtocol::new("measurement")
+                .add_field("field", i)
+                .with_timestamp(i64::from(i));
+            sink.append(&line_protocol).unwrap();
+        }
+
+        let result = sink.append(&LineProtocol::new("measurement"));
+        assert!(result.is_err());
+        sink.flush_sync().unwrap();
+
+        // Reopening appends to the existing file
+        let mut sink = FileSink::open(&path).unwrap();
+        sink.append(&LineProtocol::new("measurement").add_field("field", true))
+            .unwrap();
+        sink.flush_sync().unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                "measurement field=0i 0",
+                "measurement field=1i 1",
+                "measurement field=2i 2",
+                "measurement field=true",
+            ]
+        );
+    }
+}
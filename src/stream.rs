@@ -0,0 +1,122 @@
+//! Streaming methods process line protocol lines one at a time without
+//! materializing the whole input in memory, e.g., when reading from stdin or a
+//! large file
+
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    error::{LineProtocolError, ParseError},
+    LineProtocol,
+};
+
+/// Parse each line from the reader, apply a transformation to the parsed data
+/// point, and write the rebuilt line to the writer
+///
+/// The transformation can drop a data point by returning `None`. Empty lines
+/// and comment lines are skipped and not written to the writer
+///
+/// A line which fails to parse, or a transformed data point which fails to
+/// build, stops the stream and is returned as an
+/// [InvalidData](io::ErrorKind::InvalidData) error mentioning the line number
+///
+/// # Example
+/// ```rust
+/// let stdin = std::io::stdin().lock();
+/// let stdout = std::io::stdout().lock();
+///
+/// transform_stream(stdin, stdout, |lp| {
+///     lp.get_field_ref("value").is_some().then_some(lp)
+/// })
+/// .unwrap();
+/// ```
+///
+/// # Args
+/// * `reader` - The source of the line protocol lines
+/// * `writer` - The destination of the rebuilt line protocol lines
+/// * `f` - The transformation applied to each data point
+pub fn transform_stream<R, W, F>(reader: R, mut writer: W, mut f: F) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(LineProtocol) -> Option<LineProtocol>,
+{
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        let invalid_data = |e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("error on line {}: {e}", index + 1),
+            )
+        };
+
+        let parsed_line = match LineProtocol::parse_line(&line) {
+            Ok(parsed_line) => parsed_line,
+            Err(LineProtocolError::ParserError(
+                ParseError::CommentLine | ParseError::EmptyLine,
+            )) => continue,
+            Err(e) => return Err(invalid_data(e)),
+        };
+
+        let Some(transformed) = f(parsed_line) else {
+            continue;
+        };
+
+        let built = transformed.build().map_err(invalid_data)?;
+        writer.write_all(built.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::element::{FieldValue, TagValue};
+
+    use super::*;
+
+    #[test]
+    fn test_stream_transform() {
+        let input = [
+            "# comment",
+            "cpu,host=a usage=0.5 1",
+            "",
+            "cpu,host=b usage=0.9 2",
+            "cpu,host=c usage=0.1 3",
+        ]
+        .join("\n");
+
+        let mut output = Vec::new();
+        let result = transform_stream(input.as_bytes(), &mut output, |mut lp| {
+            // Drop data points with a high usage
+            if lp.get_field("usage") == Some(FieldValue::Float(0.9)) {
+                return None;
+            }
+
+            if let Some(host) = lp.get_tag_mut("host") {
+                *host = TagValue::from(format!("server-{host}"));
+            }
+            Some(lp)
+        });
+        assert!(result.is_ok());
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            output,
+            "cpu,host=server-a usage=0.5 1\ncpu,host=server-c usage=0.1 3\n"
+        );
+    }
+
+    #[test]
+    fn test_stream_transform_invalid_line_is_err() {
+        let input = "cpu usage=0.5 1\ncpu usage=0.5 timestamp";
+
+        let mut output = Vec::new();
+        let result = transform_stream(input.as_bytes(), &mut output, Some);
+
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("error on line 2"));
+    }
+}
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{borrow::Cow, str::FromStr};
 
 pub trait Format {
     /// Escapes [special character](https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/#special-characters) in the string
@@ -6,6 +6,19 @@ pub trait Format {
 
     /// Unescapes the escaped string in reverse order
     fn unescape(&self) -> Self;
+
+    /// Escapes the string like [Format::escape], only allocating if the
+    /// string contains special characters
+    fn escape_cow(&self) -> Cow<'_, str>;
+
+    /// Unescapes the string like [Format::unescape], only allocating if the
+    /// string contains escaped characters
+    fn unescape_cow(&self) -> Cow<'_, str>;
+
+    /// Returns the amount of extra bytes escaping the string adds
+    ///
+    /// Useful for sizing a buffer before escaping
+    fn escape_overhead(&self) -> usize;
 }
 
 pub trait Convert {
@@ -14,6 +27,16 @@ pub trait Convert {
         Self: Sized,
         T: ToString;
 
+    /// Parses like [Convert::parse_from] but only accepts values as written in
+    /// line protocol, by default equal to [Convert::parse_from]
+    fn parse_from_strict<T>(from: T) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+        T: ToString,
+    {
+        Self::parse_from(from)
+    }
+
     fn parse_into<T>(&self) -> anyhow::Result<T>
     where
         T: FromStr,